@@ -0,0 +1,309 @@
+//! Derive macro for custom partition type enums of [`nogpt`](https://docs.rs/nogpt).
+//!
+//! Use it through the `derive` feature of `nogpt`, which re-exports it as
+//! `nogpt::part::GptTypeGuid`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr, Result, Token};
+
+/// Implement `nogpt::part::GPTTypeGuid` for an enum of partition types.
+///
+/// Every unit variant needs a `#[guid("...")]` attribute with its type guid, and can set a
+/// human-readable name with `#[guid("...", name = "...")]`. The name defaults to the name of the
+/// variant. The operating system and CPU architecture of the type are set with `os = ...` and
+/// `arch = ...`, naming a variant of `nogpt::part::OsFamily` and `nogpt::part::Arch`, and are
+/// unknown without them. A single tuple variant holding a `nogpt::GUID` can be marked
+/// `#[unknown]`, to catch all other type guids. Its metadata is looked up in the registry of
+/// `nogpt`.
+///
+/// Generates the conversions from and into `[u8; 16]`, the `GPTTypeGuid` implementation with the
+/// names, operating systems and architectures of the variants, and `Display`. Without an
+/// `#[unknown]` variant, converting an unknown type guid fails with `GPTError::InvalidData`, so
+/// the enum should have a variant for the unused type guid
+/// `00000000-0000-0000-0000-000000000000`.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, PartialEq, Eq, GptTypeGuid)]
+/// enum VendorType {
+///     #[guid("00000000-0000-0000-0000-000000000000", name = "Unused entry")]
+///     Unused,
+///     #[guid("0FC63DAF-8483-4772-8E79-3D69D8477DE4", os = Linux)]
+///     Data,
+///     #[guid("8E1C31A6-5D4B-4C7B-A5B0-3F7A2C9D1E64", name = "Vendor root", arch = Arm64)]
+///     Root,
+///     #[unknown]
+///     Unknown(nogpt::GUID),
+/// }
+/// ```
+#[proc_macro_derive(GptTypeGuid, attributes(guid, unknown))]
+pub fn derive_gpt_type_guid(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Arguments of the `#[guid("...", name = "...", os = ..., arch = ...)]` attribute.
+struct GuidAttr {
+    guid: LitStr,
+    name: Option<LitStr>,
+    os: Option<Ident>,
+    arch: Option<Ident>,
+}
+
+impl Parse for GuidAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attr = Self {
+            guid: input.parse()?,
+            name: None,
+            os: None,
+            arch: None,
+        };
+
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let duplicate = if key == "name" {
+                attr.name.replace(input.parse()?).is_some()
+            } else if key == "os" {
+                attr.os.replace(input.parse()?).is_some()
+            } else if key == "arch" {
+                attr.arch.replace(input.parse()?).is_some()
+            } else {
+                return Err(Error::new(key.span(), "expected `name`, `os` or `arch`"));
+            };
+            if duplicate {
+                return Err(Error::new(key.span(), format!("`{}` is set twice", key)));
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+
+        Ok(attr)
+    }
+}
+
+/// Variant with a known type guid.
+struct KnownVariant {
+    ident: Ident,
+    guid: [u8; 16],
+    name: LitStr,
+    os: Option<Ident>,
+    arch: Option<Ident>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let data = match input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                input.ident,
+                "GptTypeGuid can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut known: Vec<KnownVariant> = Vec::new();
+    let mut unknown: Option<Ident> = None;
+
+    for variant in data.variants {
+        let guid_attr = variant.attrs.iter().find(|a| a.path.is_ident("guid"));
+        let unknown_attr = variant.attrs.iter().find(|a| a.path.is_ident("unknown"));
+
+        match (guid_attr, unknown_attr) {
+            (Some(attr), None) => {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(
+                        variant.fields,
+                        "variants with a `#[guid]` cannot have fields",
+                    ));
+                }
+
+                let args: GuidAttr = attr.parse_args()?;
+                let guid = parse_guid(&args.guid.value())
+                    .ok_or_else(|| Error::new(args.guid.span(), "invalid guid"))?;
+                if let Some(other) = known.iter().find(|v| v.guid == guid) {
+                    return Err(Error::new(
+                        args.guid.span(),
+                        format!("guid is already used by `{}`", other.ident),
+                    ));
+                }
+
+                let name = args
+                    .name
+                    .unwrap_or_else(|| LitStr::new(&variant.ident.to_string(), Span::call_site()));
+                known.push(KnownVariant {
+                    ident: variant.ident,
+                    guid,
+                    name,
+                    os: args.os,
+                    arch: args.arch,
+                });
+            }
+            (None, Some(attr)) => {
+                if unknown.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "only one variant can be marked `#[unknown]`",
+                    ));
+                }
+                match &variant.fields {
+                    Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            variant,
+                            "the `#[unknown]` variant has to hold a single `nogpt::GUID`",
+                        ))
+                    }
+                }
+                unknown = Some(variant.ident);
+            }
+            (Some(_), Some(attr)) => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "a variant cannot have both `#[guid]` and `#[unknown]`",
+                ))
+            }
+            (None, None) => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "missing `#[guid(\"...\")]` or `#[unknown]` attribute",
+                ))
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let consts: Vec<Ident> = (0..known.len())
+        .map(|i| format_ident!("GUID_{}", i))
+        .collect();
+    let guids = known.iter().map(|v| {
+        let bytes = v.guid;
+        quote!([#(#bytes),*])
+    });
+    let const_defs = quote! {
+        #(const #consts: [u8; 16] = #guids;)*
+    };
+    let idents: Vec<&Ident> = known.iter().map(|v| &v.ident).collect();
+    let names = known.iter().map(|v| &v.name);
+    let oses = known.iter().map(|v| match &v.os {
+        Some(os) => quote!(::core::option::Option::Some(::nogpt::part::OsFamily::#os)),
+        None => quote!(::core::option::Option::None),
+    });
+    let arches = known.iter().map(|v| match &v.arch {
+        Some(arch) => quote!(::core::option::Option::Some(::nogpt::part::Arch::#arch)),
+        None => quote!(::core::option::Option::None),
+    });
+
+    let from_bytes = match &unknown {
+        Some(unknown) => quote! {
+            impl #impl_generics ::core::convert::From<[u8; 16]> for #ident #ty_generics #where_clause {
+                fn from(value: [u8; 16]) -> Self {
+                    #const_defs
+                    match value {
+                        #(#consts => #ident::#idents,)*
+                        value => #ident::#unknown(::nogpt::GUID::from(value)),
+                    }
+                }
+            }
+        },
+        None => quote! {
+            impl #impl_generics ::core::convert::TryFrom<[u8; 16]> for #ident #ty_generics #where_clause {
+                type Error = ::nogpt::GPTError;
+
+                fn try_from(value: [u8; 16]) -> ::core::result::Result<Self, Self::Error> {
+                    #const_defs
+                    match value {
+                        #(#consts => ::core::result::Result::Ok(#ident::#idents),)*
+                        _ => ::core::result::Result::Err(::nogpt::GPTError::InvalidData),
+                    }
+                }
+            }
+        },
+    };
+
+    let (into_unknown, name_unknown, os_unknown, arch_unknown) = match &unknown {
+        Some(unknown) => (
+            quote!(#ident::#unknown(guid) => guid.as_bytes(),),
+            quote!(#ident::#unknown(guid) => ::nogpt::part::GPTTypeGuid::name(guid),),
+            quote!(#ident::#unknown(guid) => ::nogpt::part::GPTTypeGuid::os(guid),),
+            quote!(#ident::#unknown(guid) => ::nogpt::part::GPTTypeGuid::arch(guid),),
+        ),
+        None => (quote!(), quote!(), quote!(), quote!()),
+    };
+
+    Ok(quote! {
+        #from_bytes
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for [u8; 16] #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                #const_defs
+                match value {
+                    #(#ident::#idents => #consts,)*
+                    #into_unknown
+                }
+            }
+        }
+
+        impl #impl_generics ::nogpt::part::GPTTypeGuid for #ident #ty_generics #where_clause {
+            fn name(&self) -> ::core::option::Option<&'static str> {
+                match self {
+                    #(#ident::#idents => ::core::option::Option::Some(#names),)*
+                    #name_unknown
+                }
+            }
+
+            fn os(&self) -> ::core::option::Option<::nogpt::part::OsFamily> {
+                match self {
+                    #(#ident::#idents => #oses,)*
+                    #os_unknown
+                }
+            }
+
+            fn arch(&self) -> ::core::option::Option<::nogpt::part::Arch> {
+                match self {
+                    #(#ident::#idents => #arches,)*
+                    #arch_unknown
+                }
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&::nogpt::part::GPTTypeGuid::display(self), f)
+            }
+        }
+    })
+}
+
+/// Parse a guid in the `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` format into its on-disk layout.
+fn parse_guid(s: &str) -> Option<[u8; 16]> {
+    let parts: Vec<&str> = s.split('-').collect();
+    let lens: Vec<usize> = parts.iter().map(|p| p.len()).collect();
+    if lens != [8, 4, 4, 4, 12] || !s.chars().all(|c| c == '-' || c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let data1 = u32::from_str_radix(parts[0], 16).ok()?;
+    let data2 = u16::from_str_radix(parts[1], 16).ok()?;
+    let data3 = u16::from_str_radix(parts[2], 16).ok()?;
+
+    let mut guid = [0u8; 16];
+    guid[0..4].copy_from_slice(&data1.to_le_bytes());
+    guid[4..6].copy_from_slice(&data2.to_le_bytes());
+    guid[6..8].copy_from_slice(&data3.to_le_bytes());
+
+    let data4 = [parts[3], parts[4]].concat();
+    for (i, b) in guid[8..].iter_mut().enumerate() {
+        *b = u8::from_str_radix(&data4[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(guid)
+}
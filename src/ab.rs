@@ -0,0 +1,124 @@
+//! A/B slot selection with the attributes of ChromeOS kernel partitions.
+//!
+//! Every slot is a partition with the [`DefaultGPTTypeGuid::ChromeOSKernel`] type. The
+//! type-specific attribute bits hold the boot priority, the number of remaining boot attempts and
+//! whether the slot booted successfully before, see [`ChromeOSAttributes`].
+//!
+//! A boot loader picks a slot with [`select_slot`], and calls [`try_boot`] before booting it.
+//! Once the system is up, [`mark_successful`] is called, or [`mark_failed`] to fall back to the
+//! other slot. All functions work without allocations, using `buf` as scratch space of at least
+//! one block.
+
+use block_device::BlockDevice;
+
+use crate::part::{Attributes, ChromeOSAttributes, DefaultGPTTypeGuid};
+use crate::{GPTError, Result, GPT};
+
+/// Bootable slot found by [`select_slot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    /// Index of the partition entry.
+    pub idx: u32,
+    /// Decoded type-specific attribute bits of the partition.
+    pub attrs: ChromeOSAttributes,
+}
+
+impl Slot {
+    /// A slot is bootable if it has a priority, and either booted successfully before or has
+    /// boot attempts left.
+    pub fn is_bootable(&self) -> bool {
+        self.attrs.priority() > 0 && (self.attrs.successful() || self.attrs.tries() > 0)
+    }
+}
+
+/// Find the bootable slot with the highest priority. If several slots have the same priority,
+/// the one with the lowest index is used.
+pub fn select_slot<T>(gpt: &GPT<T>, buf: &mut [u8]) -> Result<Option<Slot>>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    let mut selected: Option<Slot> = None;
+
+    for part in gpt.used_partitions_with_buf::<DefaultGPTTypeGuid, Attributes>(buf) {
+        let (idx, part) = part?;
+        if part.type_guid != DefaultGPTTypeGuid::ChromeOSKernel {
+            continue;
+        }
+
+        let slot = Slot {
+            idx,
+            attrs: ChromeOSAttributes(part.attrs.type_specific()),
+        };
+        let better = match selected {
+            Some(selected) => slot.attrs.priority() > selected.attrs.priority(),
+            None => true,
+        };
+        if slot.is_bootable() && better {
+            selected = Some(slot);
+        }
+    }
+
+    Ok(selected)
+}
+
+/// Count a boot attempt of the slot at `idx`, by decrementing its remaining tries unless it
+/// booted successfully before. Returns the updated slot.
+pub fn try_boot<T>(gpt: &mut GPT<T>, idx: u32, buf: &mut [u8]) -> Result<Slot>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    update_slot(gpt, idx, buf, |attrs| {
+        if !attrs.successful() && attrs.tries() > 0 {
+            attrs.set_tries(attrs.tries() - 1);
+        }
+    })
+}
+
+/// Mark the slot at `idx` as booted successfully. It no longer needs boot attempts.
+pub fn mark_successful<T>(gpt: &mut GPT<T>, idx: u32, buf: &mut [u8]) -> Result<Slot>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    update_slot(gpt, idx, buf, |attrs| {
+        attrs.set_successful(true);
+        attrs.set_tries(0);
+    })
+}
+
+/// Demote the failed slot at `idx`, so it is no longer selected.
+pub fn mark_failed<T>(gpt: &mut GPT<T>, idx: u32, buf: &mut [u8]) -> Result<Slot>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    update_slot(gpt, idx, buf, |attrs| {
+        attrs.set_priority(0);
+        attrs.set_tries(0);
+        attrs.set_successful(false);
+    })
+}
+
+/// Change the attributes of the slot at `idx` with `f`, and write the entry to both partition
+/// tables.
+fn update_slot<T, F>(gpt: &mut GPT<T>, idx: u32, buf: &mut [u8], f: F) -> Result<Slot>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+    F: FnOnce(&mut ChromeOSAttributes),
+{
+    let mut part = gpt.get_partition_with_buf::<DefaultGPTTypeGuid, Attributes>(idx, buf)?;
+    if part.type_guid != DefaultGPTTypeGuid::ChromeOSKernel {
+        return Err(GPTError::InvalidData);
+    }
+
+    let mut attrs = ChromeOSAttributes(part.attrs.type_specific());
+    f(&mut attrs);
+    part.attrs.set_type_specific(attrs.0);
+
+    gpt.write_partition_with_buf::<DefaultGPTTypeGuid, Attributes>(idx, &part, buf)?;
+
+    Ok(Slot { idx, attrs })
+}
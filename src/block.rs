@@ -0,0 +1,10 @@
+use block_device::BlockDevice;
+
+/// Extension of [`BlockDevice`] for devices which know their size.
+///
+/// Knowing the size allows to find the backup header independently of the primary one, and to
+/// check the headers and the protective MBR against the real size of the device.
+pub trait BlockDeviceSize: BlockDevice {
+    /// Number of logical blocks of size [`BlockDevice::BLOCK_SIZE`] on the device.
+    fn num_blocks(&self) -> Result<u64, Self::Error>;
+}
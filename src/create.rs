@@ -0,0 +1,211 @@
+use block_device::BlockDevice;
+use crc::{crc32, Hasher32};
+
+use crate::header::{GPTHeader, HEADER_SIZE, MAX_HEADER_SIZE};
+use crate::mbr::MasterBootRecord;
+use crate::{ceil64, GPTError, Result, GPT, GUID};
+
+/// Layout of a new GUID partition table, used by [`GPT::create`].
+///
+/// Only the disk GUID and the size of the device are required, everything else defaults to the
+/// layout also used by `sgdisk`: 128 entries of 128 bytes each, and the usable area spanning from
+/// the end of the primary partition entry array to the start of the backup one.
+#[derive(Debug, Clone)]
+pub struct GPTCreateOptions {
+    guid: GUID,
+    num_blocks: u64,
+    num_parts: u32,
+    size_of_p_entry: u32,
+    first_lba: Option<u64>,
+    last_lba: Option<u64>,
+}
+
+impl GPTCreateOptions {
+    /// Default number of partition entries.
+    pub const DEFAULT_NUM_PARTS: u32 = 128;
+    /// Default size of a single partition entry.
+    pub const DEFAULT_SIZE_OF_P_ENTRY: u32 = 128;
+
+    /// Create options for a disk identified by `guid`, with `num_blocks` logical blocks.
+    pub fn new(guid: GUID, num_blocks: u64) -> Self {
+        Self {
+            guid,
+            num_blocks,
+            num_parts: Self::DEFAULT_NUM_PARTS,
+            size_of_p_entry: Self::DEFAULT_SIZE_OF_P_ENTRY,
+            first_lba: None,
+            last_lba: None,
+        }
+    }
+
+    /// Set the number of entries in the partition entry array.
+    pub fn num_parts(mut self, num_parts: u32) -> Self {
+        self.num_parts = num_parts;
+        self
+    }
+
+    /// Set the size of a single partition entry. Has to be 128 x 2^n.
+    pub fn size_of_p_entry(mut self, size_of_p_entry: u32) -> Self {
+        self.size_of_p_entry = size_of_p_entry;
+        self
+    }
+
+    /// Set the first logical block usable by partitions.
+    pub fn first_lba(mut self, first_lba: u64) -> Self {
+        self.first_lba = Some(first_lba);
+        self
+    }
+
+    /// Set the last logical block usable by partitions.
+    pub fn last_lba(mut self, last_lba: u64) -> Self {
+        self.last_lba = Some(last_lba);
+        self
+    }
+
+    /// Calculate the primary and backup header for a device with the given block size.
+    /// The partition crc is set to `p_crc32`, the header crc is calculated.
+    fn headers(&self, block_size: u32, p_crc32: u32) -> Result<(GPTHeader, GPTHeader)> {
+        // 128 x 2^n is the same as a power of two of at least 128.
+        if self.size_of_p_entry < 128
+            || !self.size_of_p_entry.is_power_of_two()
+            || self.num_parts == 0
+        {
+            return Err(GPTError::InvalidData);
+        }
+
+        let p_table_size = self.num_parts as u64 * self.size_of_p_entry as u64;
+        let p_table_blocks = ceil64(p_table_size, block_size as u64);
+
+        // MBR, primary header and entries, backup entries and header.
+        if self.num_blocks < 3 + 2 * p_table_blocks {
+            return Err(GPTError::InvalidLba(self.num_blocks));
+        }
+
+        let backup_lba = self.num_blocks - 1;
+        let backup_p_entry_lba = backup_lba - p_table_blocks;
+
+        let first_lba = self.first_lba.unwrap_or(2 + p_table_blocks);
+        let last_lba = self.last_lba.unwrap_or(backup_p_entry_lba - 1);
+        if first_lba < 2 + p_table_blocks {
+            return Err(GPTError::InvalidLba(first_lba));
+        }
+        if last_lba >= backup_p_entry_lba || last_lba < first_lba {
+            return Err(GPTError::InvalidLba(last_lba));
+        }
+
+        let mut primary = GPTHeader {
+            size: HEADER_SIZE,
+            crc32: 0,
+            my_lba: 1,
+            other_lba: backup_lba,
+            first_lba,
+            last_lba,
+            guid: self.guid,
+            p_entry_lba: 2,
+            num_parts: self.num_parts,
+            size_of_p_entry: self.size_of_p_entry,
+            p_crc32,
+            reserved: [0; (MAX_HEADER_SIZE - HEADER_SIZE) as usize],
+        };
+        primary.crc32 = primary.calculate_crc();
+
+        let mut backup = GPTHeader {
+            my_lba: backup_lba,
+            other_lba: 1,
+            p_entry_lba: backup_p_entry_lba,
+            ..primary
+        };
+        backup.crc32 = backup.calculate_crc();
+
+        Ok((primary, backup))
+    }
+}
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Write a new, empty GUID partition table to `block`.
+    ///
+    /// This writes a protective MBR, the primary header and partition entry array and the backup
+    /// partition entry array and header at the end of the disk. Any existing data in those
+    /// places is overwritten.
+    pub fn create(block: T, options: GPTCreateOptions) -> Result<Self> {
+        #[cfg(not(feature = "alloc"))]
+        let mut buf = [0u8; crate::DEFAULT_PARTTABLE_SIZE as usize];
+
+        #[cfg(feature = "alloc")]
+        let mut buf = {
+            let mut buf = alloc::vec::Vec::new();
+            buf.try_reserve_exact(crate::DEFAULT_PARTTABLE_SIZE as usize)?; // Catch allocation errors
+            buf.resize(crate::DEFAULT_PARTTABLE_SIZE as usize, 0);
+            buf
+        };
+
+        Self::create_with_buf(block, options, &mut buf)
+    }
+
+    /// Like [`Self::create`], but uses `buf` as scratch space. `buf` has to hold at least one
+    /// logical block, the partition entry arrays are written in chunks of the buffer size.
+    pub fn create_with_buf(block: T, options: GPTCreateOptions, buf: &mut [u8]) -> Result<Self> {
+        let block_size = T::BLOCK_SIZE as usize;
+        let chunk_blocks = buf.len() / block_size;
+        if chunk_blocks == 0 {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        let p_table_size = options.num_parts as u64 * options.size_of_p_entry as u64;
+
+        // The crc of the empty partition entry array.
+        let p_crc32 = {
+            let buf = &mut buf[..chunk_blocks * block_size];
+            buf.fill(0);
+
+            let mut digest = crc32::Digest::new(crc32::IEEE);
+            let mut left = p_table_size;
+            while left > 0 {
+                let len = core::cmp::min(left, buf.len() as u64);
+                digest.write(&buf[..len as usize]);
+                left -= len;
+            }
+            digest.sum32()
+        };
+
+        let (primary, backup) = options.headers(T::BLOCK_SIZE, p_crc32)?;
+        let p_table_blocks = ceil64(p_table_size, block_size as u64);
+
+        for lba in [primary.p_entry_lba, backup.p_entry_lba] {
+            let buf = &buf[..chunk_blocks * block_size];
+            let mut written = 0;
+            while written < p_table_blocks {
+                let blocks = core::cmp::min(p_table_blocks - written, chunk_blocks as u64);
+                block.write(
+                    &buf[..blocks as usize * block_size],
+                    (lba + written) as usize,
+                    blocks as usize,
+                )?;
+                written += blocks;
+            }
+        }
+
+        for header in [&backup, &primary] {
+            let buf = &mut buf[..block_size];
+            buf.fill(0);
+            header.write_to(buf)?;
+            block.write(buf, header.my_lba as usize, 1)?;
+        }
+
+        // Write the MBR last, so the disk is only recognized once everything is in place.
+        let buf = &mut buf[..block_size];
+        buf.fill(0);
+        let mbr = MasterBootRecord::new_protective(options.num_blocks);
+        mbr.write_to(buf)?;
+        block.write(buf, 0, 1)?;
+
+        let mut gpt = Self::new(block, primary, backup.p_entry_lba);
+        gpt.mbr_partition = mbr.partition;
+
+        Ok(gpt)
+    }
+}
@@ -0,0 +1,314 @@
+//! Discovery of partitions following the discoverable partitions specification (DPS), as done
+//! by `systemd-gpt-auto-generator`.
+//!
+//! Partitions with the `no-auto` attribute bit, or named `_empty`, are ignored. If several root,
+//! `/usr` or verity partitions exist for the same architecture, the one with the highest version
+//! in its name is used, and the first one if the versions are equal. Of several ESP or XBOOTLDR
+//! partitions the first one is used.
+//!
+//! A root or `/usr` partition is paired with its verity partition through the dm-verity root
+//! hash: the partition GUID of the data partition is the first 128 bits of the root hash, the
+//! one of the verity partition the last 128 bits.
+
+use core::cmp::Ordering;
+
+use block_device::BlockDevice;
+
+use crate::part::{Arch, DefaultGPTTypeGuid, GPTPartHeader, SystemdAttributes};
+use crate::{GPTError, Result, GPT, GUID};
+
+/// Partition found by the discoverable partitions helpers of [`GPT`].
+#[derive(Debug)]
+pub struct DpsPartition {
+    /// Index of the partition entry.
+    pub idx: u32,
+    /// The partition entry.
+    pub part: GPTPartHeader,
+    /// Decoded type-specific attribute bits of the partition.
+    pub attrs: SystemdAttributes,
+}
+
+impl DpsPartition {
+    /// The partition should be mounted read-only.
+    pub fn read_only(&self) -> bool {
+        self.attrs.read_only()
+    }
+
+    /// The file system should be grown to the size of the partition when mounting it.
+    pub fn grow_fs(&self) -> bool {
+        self.attrs.grow_fs()
+    }
+
+    fn name(&self) -> &[u16] {
+        name(&self.part.name)
+    }
+}
+
+/// How to choose between several partitions of the same type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Select {
+    /// The first partition.
+    First,
+    /// The partition with the highest version.
+    Newest,
+    /// The first partition with the partition GUID `guid`.
+    Guid(GUID),
+}
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Find the root partition for `arch`.
+    pub fn find_root_for_arch(&self, arch: Arch) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions()?;
+        find_dps(parts, DefaultGPTTypeGuid::LinuxRoot(arch), Select::Newest)
+    }
+
+    /// Like [`Self::find_root_for_arch`], but reads the partition entry array in chunks into
+    /// `buf`. `buf` has to hold at least one block.
+    pub fn find_root_for_arch_with_buf(
+        &self,
+        arch: Arch,
+        buf: &mut [u8],
+    ) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions_with_buf(buf);
+        find_dps(parts, DefaultGPTTypeGuid::LinuxRoot(arch), Select::Newest)
+    }
+
+    /// Find the `/usr` partition for `arch`.
+    pub fn find_usr(&self, arch: Arch) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions()?;
+        find_dps(parts, DefaultGPTTypeGuid::LinuxUsr(arch), Select::Newest)
+    }
+
+    /// Like [`Self::find_usr`], but reads the partition entry array in chunks into `buf`. `buf`
+    /// has to hold at least one block.
+    pub fn find_usr_with_buf(&self, arch: Arch, buf: &mut [u8]) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions_with_buf(buf);
+        find_dps(parts, DefaultGPTTypeGuid::LinuxUsr(arch), Select::Newest)
+    }
+
+    /// Find the EFI system partition.
+    pub fn find_esp(&self) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions()?;
+        find_dps(parts, DefaultGPTTypeGuid::ESP, Select::First)
+    }
+
+    /// Like [`Self::find_esp`], but reads the partition entry array in chunks into `buf`. `buf`
+    /// has to hold at least one block.
+    pub fn find_esp_with_buf(&self, buf: &mut [u8]) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions_with_buf(buf);
+        find_dps(parts, DefaultGPTTypeGuid::ESP, Select::First)
+    }
+
+    /// Find the extended boot loader partition.
+    pub fn find_xbootldr(&self) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions()?;
+        find_dps(parts, DefaultGPTTypeGuid::XBootLdr, Select::First)
+    }
+
+    /// Like [`Self::find_xbootldr`], but reads the partition entry array in chunks into `buf`.
+    /// `buf` has to hold at least one block.
+    pub fn find_xbootldr_with_buf(&self, buf: &mut [u8]) -> Result<Option<DpsPartition>> {
+        let parts = self.used_partitions_with_buf(buf);
+        find_dps(parts, DefaultGPTTypeGuid::XBootLdr, Select::First)
+    }
+
+    /// Find the dm-verity hash partition of the root or `/usr` partition `data`, with the
+    /// dm-verity `root_hash` of `data`.
+    ///
+    /// Returns `None` if the partition GUID of `data` doesn't match `root_hash`, or if there is
+    /// no verity partition with the GUID derived from `root_hash`. Fails with
+    /// [`GPTError::InvalidData`] if `data` is neither a root nor a `/usr` partition, or if
+    /// `root_hash` is shorter than 128 bits.
+    pub fn find_verity_for(
+        &self,
+        data: &DpsPartition,
+        root_hash: &[u8],
+    ) -> Result<Option<DpsPartition>> {
+        let verity = verity_type(data)?;
+        let select = match verity_guid(data, root_hash)? {
+            Some(guid) => Select::Guid(guid),
+            None => return Ok(None),
+        };
+        let parts = self.used_partitions()?;
+        find_dps(parts, verity, select)
+    }
+
+    /// Like [`Self::find_verity_for`], but reads the partition entry array in chunks into `buf`.
+    /// `buf` has to hold at least one block.
+    pub fn find_verity_for_with_buf(
+        &self,
+        data: &DpsPartition,
+        root_hash: &[u8],
+        buf: &mut [u8],
+    ) -> Result<Option<DpsPartition>> {
+        let verity = verity_type(data)?;
+        let select = match verity_guid(data, root_hash)? {
+            Some(guid) => Select::Guid(guid),
+            None => return Ok(None),
+        };
+        let parts = self.used_partitions_with_buf(buf);
+        find_dps(parts, verity, select)
+    }
+}
+
+/// Type of the verity partition of the root or `/usr` partition `data`.
+fn verity_type(data: &DpsPartition) -> Result<DefaultGPTTypeGuid> {
+    match data.part.type_guid {
+        DefaultGPTTypeGuid::LinuxRoot(arch) => Ok(DefaultGPTTypeGuid::LinuxRootVerity(arch)),
+        DefaultGPTTypeGuid::LinuxUsr(arch) => Ok(DefaultGPTTypeGuid::LinuxUsrVerity(arch)),
+        _ => Err(GPTError::InvalidData),
+    }
+}
+
+/// Partition GUID of the verity partition of `data`, or `None` if the partition GUID of `data`
+/// doesn't belong to `root_hash`.
+fn verity_guid(data: &DpsPartition, root_hash: &[u8]) -> Result<Option<GUID>> {
+    if root_hash.len() < 16 {
+        return Err(GPTError::InvalidData);
+    }
+
+    let data_guid = guid_from_hash(&root_hash[..16]);
+    let verity_guid = guid_from_hash(&root_hash[root_hash.len() - 16..]);
+    if data.part.guid == data_guid {
+        Ok(Some(verity_guid))
+    } else {
+        Ok(None)
+    }
+}
+
+/// GUID whose string representation is the hex encoding of the 16 bytes in `bytes`.
+fn guid_from_hash(bytes: &[u8]) -> GUID {
+    let mut data1 = [0u8; 4];
+    let mut data2 = [0u8; 2];
+    let mut data3 = [0u8; 2];
+    let mut data4 = [0u8; 8];
+    data1.copy_from_slice(&bytes[..4]);
+    data2.copy_from_slice(&bytes[4..6]);
+    data3.copy_from_slice(&bytes[6..8]);
+    data4.copy_from_slice(&bytes[8..16]);
+
+    GUID::new(
+        u32::from_be_bytes(data1),
+        u16::from_be_bytes(data2),
+        u16::from_be_bytes(data3),
+        u64::from_be_bytes(data4),
+    )
+}
+
+/// Choose the partition of type `type_guid` out of `parts`.
+fn find_dps<I>(
+    parts: I,
+    type_guid: DefaultGPTTypeGuid,
+    select: Select,
+) -> Result<Option<DpsPartition>>
+where
+    I: Iterator<Item = Result<(u32, GPTPartHeader)>>,
+{
+    let mut found: Option<DpsPartition> = None;
+
+    for part in parts {
+        let (idx, part) = part?;
+        if part.type_guid != type_guid {
+            continue;
+        }
+
+        let attrs = SystemdAttributes(part.attrs.type_specific());
+        let candidate = DpsPartition { idx, part, attrs };
+        if candidate.attrs.no_auto() || candidate.name() == EMPTY_NAME {
+            continue;
+        }
+
+        let better = match (&found, select) {
+            (_, Select::Guid(guid)) => found.is_none() && candidate.part.guid == guid,
+            (None, _) => true,
+            (Some(_), Select::First) => false,
+            (Some(found), Select::Newest) => {
+                version_cmp(candidate.name(), found.name()) == Ordering::Greater
+            }
+        };
+        if better {
+            found = Some(candidate);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Name of unused A/B slots, as used by `systemd-sysupdate`.
+const EMPTY_NAME: &[u16] = &[
+    b'_' as u16,
+    b'e' as u16,
+    b'm' as u16,
+    b'p' as u16,
+    b't' as u16,
+    b'y' as u16,
+];
+
+/// Name of a partition without the trailing null characters.
+fn name(name: &[u16; 36]) -> &[u16] {
+    let len = name.iter().take_while(|&&c| c != 0).count();
+    &name[..len]
+}
+
+/// Compare two partition names as versions: runs of digits are compared by their numeric value,
+/// everything else by its code units.
+fn version_cmp(a: &[u16], b: &[u16]) -> Ordering {
+    let is_digit = |c: u16| (b'0' as u16..=b'9' as u16).contains(&c);
+    let (mut a, mut b) = (a, b);
+
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(&x), Some(&y)) if is_digit(x) && is_digit(y) => {
+                let a_len = a.iter().take_while(|&&c| is_digit(c)).count();
+                let b_len = b.iter().take_while(|&&c| is_digit(c)).count();
+                let (a_num, a_rest) = a.split_at(a_len);
+                let (b_num, b_rest) = b.split_at(b_len);
+
+                let a_num = &a_num[a_num.iter().take_while(|&&c| c == b'0' as u16).count()..];
+                let b_num = &b_num[b_num.iter().take_while(|&&c| c == b'0' as u16).count()..];
+                let ord = a_num.len().cmp(&b_num.len()).then_with(|| a_num.cmp(b_num));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::version_cmp;
+    use core::cmp::Ordering;
+
+    fn cmp(a: &str, b: &str) -> Ordering {
+        let a: Vec<u16> = a.encode_utf16().collect();
+        let b: Vec<u16> = b.encode_utf16().collect();
+        version_cmp(&a, &b)
+    }
+
+    #[test]
+    fn versions() {
+        assert_eq!(cmp("root_1.10", "root_1.9"), Ordering::Greater);
+        assert_eq!(cmp("root_2", "root_10"), Ordering::Less);
+        assert_eq!(cmp("root_007", "root_7"), Ordering::Equal);
+        assert_eq!(cmp("root", "root_1"), Ordering::Less);
+        assert_eq!(cmp("root_b", "root_a"), Ordering::Greater);
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use block_device::BlockDevice;
+
+use crate::part::{GPTPartHeader, GPTTypeGuid, RawPartHeader};
+use crate::{ceil64, GPTError, Result, GPT, GUID};
+
+/// Editing of the partition table.
+///
+/// All changes are staged in memory and only written to the device by [`GPT::commit`]. Lookups
+/// like [`GPT::get_partition`] keep returning the data on the device until then.
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Add a new partition in the first unused partition entry, and return its index.
+    ///
+    /// The partition has to be inside [`crate::header::GPTHeader::first_lba`] and
+    /// [`crate::header::GPTHeader::last_lba`] and must not overlap any other partition.
+    pub fn add_partition<PT, PA>(&mut self, part: &GPTPartHeader<PT, PA>) -> Result<u32>
+    where
+        PT: GPTTypeGuid + Clone,
+        GPTError: From<<PT as TryInto<[u8; 16]>>::Error>,
+        PA: TryInto<u64> + Clone,
+        GPTError: From<<PA as TryInto<u64>>::Error>,
+    {
+        let type_guid: [u8; 16] = part.type_guid.clone().try_into()?;
+        if GUID::from(type_guid) == GUID::UNUSED {
+            return Err(GPTError::InvalidData);
+        }
+
+        self.check_range(None, part.start_lba, part.end_lba)?;
+
+        let idx = (0..self.header.num_parts)
+            .find(|&idx| matches!(self.staged_entry(idx), Ok(entry) if entry.type_guid == GUID::UNUSED))
+            .ok_or(GPTError::NoFreePartitionEntry)?;
+
+        part.write_to(self.staged_slot(idx)?)?;
+
+        Ok(idx)
+    }
+
+    /// Remove the partition at `idx`, by clearing its partition entry.
+    pub fn remove_partition(&mut self, idx: u32) -> Result<()> {
+        self.used_entry(idx)?;
+        self.staged_slot(idx)?.fill(0);
+
+        Ok(())
+    }
+
+    /// Set the name of the partition at `idx`.
+    pub fn set_name(&mut self, idx: u32, name: &str) -> Result<()> {
+        let mut entry = self.used_entry(idx)?;
+        entry.set_name(name)?;
+
+        entry.write_to(self.staged_slot(idx)?)
+    }
+
+    /// Set the partition type of the partition at `idx`.
+    pub fn set_type<PT>(&mut self, idx: u32, type_guid: PT) -> Result<()>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryInto<[u8; 16]>>::Error>,
+    {
+        let type_guid: [u8; 16] = type_guid.try_into()?;
+        let type_guid = GUID::from(type_guid);
+        if type_guid == GUID::UNUSED {
+            return Err(GPTError::InvalidData);
+        }
+
+        let mut entry = self.used_entry(idx)?;
+        entry.type_guid = type_guid;
+
+        entry.write_to(self.staged_slot(idx)?)
+    }
+
+    /// Set the attributes of the partition at `idx`.
+    pub fn set_attributes<PA>(&mut self, idx: u32, attrs: PA) -> Result<()>
+    where
+        PA: TryInto<u64>,
+        GPTError: From<<PA as TryInto<u64>>::Error>,
+    {
+        let mut entry = self.used_entry(idx)?;
+        entry.attrs = attrs.try_into()?;
+
+        entry.write_to(self.staged_slot(idx)?)
+    }
+
+    /// Move the start and end of the partition at `idx`. The data of the partition is not moved.
+    pub fn resize_partition(&mut self, idx: u32, start_lba: u64, end_lba: u64) -> Result<()> {
+        let mut entry = self.used_entry(idx)?;
+        self.check_range(Some(idx), start_lba, end_lba)?;
+
+        entry.start_lba = start_lba;
+        entry.end_lba = end_lba;
+
+        entry.write_to(self.staged_slot(idx)?)
+    }
+
+    /// Check if there are staged changes, not yet written by [`Self::commit`].
+    pub fn has_staged_changes(&self) -> bool {
+        self.staged.is_some()
+    }
+
+    /// Drop all staged changes.
+    pub fn discard(&mut self) {
+        self.staged = None;
+    }
+
+    /// Write all staged changes to the device.
+    ///
+    /// The backup partition entry array and header are written first, then the primary ones. If
+    /// the write is interrupted, one copy of the partition table stays valid and can be used to
+    /// repair the other one.
+    pub fn commit(&mut self) -> Result<()> {
+        let table = match self.staged.take() {
+            Some(table) => table,
+            None => return Ok(()),
+        };
+
+        let mut buf = Vec::new();
+        buf.try_reserve_exact(T::BLOCK_SIZE as usize)?; // Catch allocation errors
+        buf.resize(T::BLOCK_SIZE as usize, 0);
+
+        if let Err(e) = self.write_table(&table, &mut buf) {
+            self.staged = Some(table);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Return the staged partition entry array, copying it from the cache or reading it from the
+    /// device on first use. A partition entry array read from the device has to match the crc32
+    /// of the header.
+    pub(crate) fn staged_table(&mut self) -> Result<&mut Vec<u8>> {
+        if let (None, Some(cache)) = (&self.staged, &self.cache) {
+            let mut table = Vec::new();
+            table.try_reserve_exact(cache.len())?; // Catch allocation errors
+            table.extend_from_slice(cache);
+
+            self.staged = Some(table);
+        }
+
+        if self.staged.is_none() {
+            let p_table_size =
+                self.header.num_parts as usize * self.header.size_of_p_entry as usize;
+            let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+
+            let mut table = Vec::new();
+            table.try_reserve_exact(blocks * T::BLOCK_SIZE as usize)?; // Catch allocation errors
+            table.resize(blocks * T::BLOCK_SIZE as usize, 0);
+
+            self.block
+                .read(&mut table, self.header.p_entry_lba as usize, blocks)?;
+            table.truncate(p_table_size);
+            // Committing writes a new crc32, which would make a broken table look valid.
+            self.header.validate_part_crc(&table)?;
+
+            self.staged = Some(table);
+        }
+
+        // Cannot fail, as it was set above
+        self.staged.as_mut().ok_or(GPTError::InvalidData)
+    }
+
+    /// Return the bytes of the staged partition entry at `idx`.
+    fn staged_slot(&mut self, idx: u32) -> Result<&mut [u8]> {
+        if idx >= self.header.num_parts {
+            return Err(GPTError::InvalidData);
+        }
+
+        let size = self.header.size_of_p_entry as usize;
+        let offset = size * idx as usize;
+
+        Ok(&mut self.staged_table()?[offset..offset + size])
+    }
+
+    fn staged_entry(&mut self, idx: u32) -> Result<RawPartHeader> {
+        RawPartHeader::parse(self.staged_slot(idx)?)
+    }
+
+    /// Return the staged partition entry at `idx`, failing if it is not used.
+    fn used_entry(&mut self, idx: u32) -> Result<RawPartHeader> {
+        let entry = self.staged_entry(idx)?;
+        if entry.type_guid == GUID::UNUSED {
+            return Err(GPTError::InvalidData);
+        }
+
+        Ok(entry)
+    }
+
+    /// Check that `start_lba..=end_lba` is usable and does not overlap any staged partition,
+    /// except the one at `skip`.
+    fn check_range(&mut self, skip: Option<u32>, start_lba: u64, end_lba: u64) -> Result<()> {
+        if start_lba < self.header.first_lba || start_lba > end_lba {
+            return Err(GPTError::InvalidLba(start_lba));
+        }
+        if end_lba > self.header.last_lba {
+            return Err(GPTError::InvalidLba(end_lba));
+        }
+
+        for idx in (0..self.header.num_parts).filter(|&idx| Some(idx) != skip) {
+            let entry = self.staged_entry(idx)?;
+            if entry.type_guid != GUID::UNUSED
+                && start_lba <= entry.end_lba
+                && entry.start_lba <= end_lba
+            {
+                return Err(GPTError::OverlappingPartitions);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::GPT;
+use block_device::BlockDevice;
 use core::convert::Infallible;
 
 #[derive(err_derive::Error, Debug)]
@@ -49,8 +50,17 @@ pub enum GPTError {
     #[error(display = "MBR partition is not valid")]
     InvalidMbr,
 
-    #[error(display = "MBR partitions are overlapping")]
+    #[error(display = "Partitions are overlapping")]
     OverlappingPartitions,
+
+    #[error(display = "No unused partition entry left")]
+    NoFreePartitionEntry,
+
+    #[error(display = "No free space after partition {}", _0)]
+    NoFreeSpace(u32),
+
+    #[error(display = "There are staged changes not yet committed")]
+    StagedChanges,
 }
 
 impl From<Infallible> for GPTError {
@@ -59,6 +69,8 @@ impl From<Infallible> for GPTError {
     }
 }
 
+// The GPT is returned by value, to allow repairing it without an allocator.
+#[allow(clippy::large_enum_variant)]
 #[derive(err_derive::Error)]
 pub enum GPTParseError<T: Sized> {
     #[error(display = "{}", _0)]
@@ -100,9 +112,14 @@ impl<T: Sized> core::fmt::Debug for GPTParseError<T> {
 pub type Result<T, E = GPTError> = core::result::Result<T, E>;
 
 pub trait GptRepair<T: Sized> {
+    /// Treat a broken header as error.
     fn fail(self) -> Result<GPT<T>>;
 
-    // TODO: implement helper methods to help repair a gpt partition
+    /// Repair a broken header from the valid one, see [`GPT::repair`].
+    fn repair(self) -> Result<GPT<T>>
+    where
+        T: BlockDevice,
+        GPTError: From<T::Error>;
 }
 
 impl<T: Sized> GptRepair<T> for Result<GPT<T>, GPTParseError<T>> {
@@ -113,6 +130,21 @@ impl<T: Sized> GptRepair<T> for Result<GPT<T>, GPTParseError<T>> {
             Err(GPTParseError::BrokenHeader(_, _, e)) => Err(e),
         }
     }
+
+    fn repair(self) -> Result<GPT<T>>
+    where
+        T: BlockDevice,
+        GPTError: From<T::Error>,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(GPTParseError::Error(e)) => Err(e),
+            Err(GPTParseError::BrokenHeader(mut gpt, broken, _)) => {
+                gpt.repair(broken)?;
+                Ok(gpt)
+            }
+        }
+    }
 }
 
 #[derive(err_derive::Error, Debug)]
@@ -51,6 +51,193 @@ impl GUID {
     pub const ESP: Self = Self::new(0xC12A7328, 0xF81F, 0x11D2, 0xBA4B00A0C93EC93B);
     /// Partition containing a legacy MBR
     pub const LEGACY_MBR: Self = Self::new(0x024DEE41, 0x33E7, 0x11D3, 0x9D690008C781F39F);
+    /// BIOS boot partition, used by GRUB on BIOS systems.
+    pub const BIOS_BOOT: Self = Self::new(0x21686148, 0x6449, 0x6E6F, 0x744E656564454649);
+    /// Extended boot loader partition (`XBOOTLDR`) of the boot loader specification.
+    pub const XBOOTLDR: Self = Self::new(0xBC13C2FF, 0x59E6, 0x4262, 0xA352B275FD6F7172);
+    /// Linux filesystem data.
+    pub const LINUX_FS: Self = Self::new(0x0FC63DAF, 0x8483, 0x4772, 0x8E793D69D8477DE4);
+    /// Linux `/home` partition.
+    pub const LINUX_HOME: Self = Self::new(0x933AC7E1, 0x2EB4, 0x4F13, 0xB8440E14E2AEF915);
+    /// Linux `/srv` partition.
+    pub const LINUX_SRV: Self = Self::new(0x3B8F8425, 0x20E0, 0x4F3B, 0x907F1A25A76F98E8);
+    /// Linux `/var` partition.
+    pub const LINUX_VAR: Self = Self::new(0x4D21B016, 0xB534, 0x45C2, 0xA9FB5C16E091FD2D);
+    /// Linux `/var/tmp` partition.
+    pub const LINUX_VAR_TMP: Self = Self::new(0x7EC6F557, 0x3BC5, 0x4ACA, 0xB29316EF5DF639D1);
+    /// Linux swap space.
+    pub const LINUX_SWAP: Self = Self::new(0x0657FD6D, 0xA4AB, 0x43C4, 0x84E50933C84B4F4F);
+    /// Linux logical volume manager physical volume.
+    pub const LINUX_LVM: Self = Self::new(0xE6D6D379, 0xF507, 0x44C2, 0xA23C238F2A3DF928);
+    /// Linux software RAID member.
+    pub const LINUX_RAID: Self = Self::new(0xA19D880F, 0x05FC, 0x4D3B, 0xA006743F0F84911E);
+    /// Linux LUKS encrypted partition.
+    pub const LINUX_LUKS: Self = Self::new(0xCA7D7CCB, 0x63ED, 0x4C53, 0x861C1742536059CC);
+    /// Linux plain dm-crypt partition.
+    pub const LINUX_DM_CRYPT: Self = Self::new(0x7FFEC5C9, 0x2D00, 0x49B7, 0x89413EA10A5586B7);
+    /// Reserved for Linux.
+    pub const LINUX_RESERVED: Self = Self::new(0x8DA63339, 0x0007, 0x60C0, 0xC436083AC8230908);
+    /// Microsoft reserved partition (MSR).
+    pub const MICROSOFT_RESERVED: Self = Self::new(0xE3C9E316, 0x0B5C, 0x4DB8, 0x817DF92DF00215AE);
+    /// Microsoft basic data partition, also used for FAT and exFAT on other systems.
+    pub const MICROSOFT_BASIC_DATA: Self =
+        Self::new(0xEBD0A0A2, 0xB9E5, 0x4433, 0x87C068B6B72699C7);
+    /// Windows recovery environment.
+    pub const WINDOWS_RECOVERY: Self = Self::new(0xDE94BBA4, 0x06D1, 0x4D40, 0xA16ABFD50179D6AC);
+    /// Logical disk manager metadata partition.
+    pub const WINDOWS_LDM_METADATA: Self =
+        Self::new(0x5808C8AA, 0x7E8F, 0x42E0, 0x85D2E1E90434CFB3);
+    /// Logical disk manager data partition.
+    pub const WINDOWS_LDM_DATA: Self = Self::new(0xAF9B60A0, 0x1431, 0x4F62, 0xBC683311714A69AD);
+    /// Windows Storage Spaces partition.
+    pub const WINDOWS_STORAGE_SPACES: Self =
+        Self::new(0xE75CAF8F, 0xF680, 0x4CEE, 0xAFA3B001E56EFC2D);
+    /// Apple HFS+ partition.
+    pub const APPLE_HFS_PLUS: Self = Self::new(0x48465300, 0x0000, 0x11AA, 0xAA1100306543ECAC);
+    /// Apple APFS container.
+    pub const APPLE_APFS: Self = Self::new(0x7C3457EF, 0x0000, 0x11AA, 0xAA1100306543ECAC);
+    /// Apple UFS partition.
+    pub const APPLE_UFS: Self = Self::new(0x55465300, 0x0000, 0x11AA, 0xAA1100306543ECAC);
+    /// Apple RAID partition.
+    pub const APPLE_RAID: Self = Self::new(0x52414944, 0x0000, 0x11AA, 0xAA1100306543ECAC);
+    /// Apple boot partition (recovery HD).
+    pub const APPLE_BOOT: Self = Self::new(0x426F6F74, 0x0000, 0x11AA, 0xAA1100306543ECAC);
+    /// Apple Core Storage container.
+    pub const APPLE_CORE_STORAGE: Self = Self::new(0x53746F72, 0x6167, 0x11AA, 0xAA1100306543ECAC);
+    /// FreeBSD boot partition.
+    pub const FREEBSD_BOOT: Self = Self::new(0x83BD6B9D, 0x7F41, 0x11DC, 0xBE0B001560B84F0F);
+    /// FreeBSD disklabel partition.
+    pub const FREEBSD_DATA: Self = Self::new(0x516E7CB4, 0x6ECF, 0x11D6, 0x8FF800022D09712B);
+    /// FreeBSD swap partition.
+    pub const FREEBSD_SWAP: Self = Self::new(0x516E7CB5, 0x6ECF, 0x11D6, 0x8FF800022D09712B);
+    /// FreeBSD UFS partition.
+    pub const FREEBSD_UFS: Self = Self::new(0x516E7CB6, 0x6ECF, 0x11D6, 0x8FF800022D09712B);
+    /// FreeBSD Vinum volume manager partition.
+    pub const FREEBSD_VINUM: Self = Self::new(0x516E7CB8, 0x6ECF, 0x11D6, 0x8FF800022D09712B);
+    /// FreeBSD ZFS partition.
+    pub const FREEBSD_ZFS: Self = Self::new(0x516E7CBA, 0x6ECF, 0x11D6, 0x8FF800022D09712B);
+    /// ChromeOS kernel partition.
+    pub const CHROMEOS_KERNEL: Self = Self::new(0xFE3A2A5D, 0x4F32, 0x41A7, 0xB725ACCC3285A309);
+    /// ChromeOS root filesystem.
+    pub const CHROMEOS_ROOTFS: Self = Self::new(0x3CB8E202, 0x3B7E, 0x47DD, 0x8A3C7FF2A13CFCEC);
+    /// ChromeOS firmware partition.
+    pub const CHROMEOS_FIRMWARE: Self = Self::new(0xCAB6E88E, 0xABF3, 0x4102, 0xA07AD4BB9BE3C1D3);
+    /// ChromeOS partition reserved for future use.
+    pub const CHROMEOS_RESERVED: Self = Self::new(0x2E0A753D, 0x9E48, 0x43B0, 0x8337B15192CB1B5E);
+    /// ChromeOS miniOS partition.
+    pub const CHROMEOS_MINIOS: Self = Self::new(0x09845860, 0x705F, 0x4BB5, 0xB16C8A8A099CAF52);
+    /// ChromeOS hibernate partition.
+    pub const CHROMEOS_HIBERNATE: Self = Self::new(0x3F0F8318, 0xF146, 0x4E6B, 0x8222C28C8F02E0D5);
+    /// Android bootloader partition.
+    pub const ANDROID_BOOTLOADER: Self = Self::new(0x2568845D, 0x2332, 0x4675, 0xBC398FA5A4748D15);
+    /// Second Android bootloader partition.
+    pub const ANDROID_BOOTLOADER2: Self = Self::new(0x114EAFFE, 0x1552, 0x4022, 0xB26E9B053604CF84);
+    /// Android boot image.
+    pub const ANDROID_BOOT: Self = Self::new(0x49A4D17F, 0x93A3, 0x45C1, 0xA0DEF50B2EBE2599);
+    /// Android recovery image.
+    pub const ANDROID_RECOVERY: Self = Self::new(0x4177C722, 0x9E92, 0x4AAB, 0x864443502BFD5506);
+    /// Android misc partition.
+    pub const ANDROID_MISC: Self = Self::new(0xEF32A33B, 0xA409, 0x486C, 0x91419FFB711F6266);
+    /// Android metadata partition.
+    pub const ANDROID_METADATA: Self = Self::new(0x20AC26BE, 0x20B7, 0x11E3, 0x84C56CFDB94711E9);
+    /// Android system partition.
+    pub const ANDROID_SYSTEM: Self = Self::new(0x38F428E6, 0xD326, 0x425D, 0x91406E0EA133647C);
+    /// Android cache partition.
+    pub const ANDROID_CACHE: Self = Self::new(0xA893EF21, 0xE428, 0x470A, 0x9E550668FD91A2D9);
+    /// Android user data partition.
+    pub const ANDROID_DATA: Self = Self::new(0xDC76DDA9, 0x5AC1, 0x491C, 0xAF42A82591580C0D);
+    /// Android persistent partition.
+    pub const ANDROID_PERSISTENT: Self = Self::new(0xEBC597D0, 0x2053, 0x4B15, 0x8B64E0AAC75F4DB1);
+    /// Android vendor partition.
+    pub const ANDROID_VENDOR: Self = Self::new(0xC5A0AEEC, 0x13EA, 0x11E5, 0xA1B1001E67CA0C3C);
+    /// Android config partition.
+    pub const ANDROID_CONFIG: Self = Self::new(0xBD59408B, 0x4514, 0x490D, 0xBF129878D963F378);
+    /// Android factory partition.
+    pub const ANDROID_FACTORY: Self = Self::new(0x8F68CC74, 0xC5E5, 0x48DA, 0xBE91A0C8C15E9C80);
+    /// Android OEM partition.
+    pub const ANDROID_OEM: Self = Self::new(0xAC6D7924, 0xEB71, 0x4DF8, 0xB48DE267B27148FF);
+    /// Linux root partition for x86.
+    pub const LINUX_ROOT_X86: Self = Self::new(0x44479540, 0xF297, 0x41B2, 0x9AF7D131D5F0458A);
+    /// Linux root partition for x86-64.
+    pub const LINUX_ROOT_X86_64: Self = Self::new(0x4F68BCE3, 0xE8CD, 0x4DB1, 0x96E7FBCAF984B709);
+    /// Linux root partition for ARM.
+    pub const LINUX_ROOT_ARM: Self = Self::new(0x69DAD710, 0x2CE4, 0x4E3C, 0xB16C21A1D49ABED3);
+    /// Linux root partition for ARM64.
+    pub const LINUX_ROOT_ARM64: Self = Self::new(0xB921B045, 0x1DF0, 0x41C3, 0xAF444C6F280D3FAE);
+    /// Linux root partition for IA-64.
+    pub const LINUX_ROOT_IA64: Self = Self::new(0x993D8D3D, 0xF80E, 0x4225, 0x855A9DAF8ED7EA97);
+    /// Linux root partition for LoongArch64.
+    pub const LINUX_ROOT_LOONGARCH64: Self =
+        Self::new(0x77055800, 0x792C, 0x4F94, 0xB39A98C91B762BB6);
+    /// Linux root partition for RISC-V 32.
+    pub const LINUX_ROOT_RISCV32: Self = Self::new(0x60D5A7FE, 0x8E7D, 0x435C, 0xB7143DD8162144E1);
+    /// Linux root partition for RISC-V 64.
+    pub const LINUX_ROOT_RISCV64: Self = Self::new(0x72EC70A6, 0xCF74, 0x40E6, 0xBD494BDA08E8F224);
+    /// Linux `/usr` partition for x86.
+    pub const LINUX_USR_X86: Self = Self::new(0x75250D76, 0x8CC6, 0x458E, 0xBD66BD47CC81A812);
+    /// Linux `/usr` partition for x86-64.
+    pub const LINUX_USR_X86_64: Self = Self::new(0x8484680C, 0x9521, 0x48C6, 0x9C11B0720656F69E);
+    /// Linux `/usr` partition for ARM.
+    pub const LINUX_USR_ARM: Self = Self::new(0x7D0359A3, 0x02B3, 0x4F0A, 0x865C654403E70625);
+    /// Linux `/usr` partition for ARM64.
+    pub const LINUX_USR_ARM64: Self = Self::new(0xB0E01050, 0xEE5F, 0x4390, 0x949A9101B17104E9);
+    /// Linux `/usr` partition for IA-64.
+    pub const LINUX_USR_IA64: Self = Self::new(0x4301D2A6, 0x4E3B, 0x4B2A, 0xBB949E0B2C4225EA);
+    /// Linux `/usr` partition for LoongArch64.
+    pub const LINUX_USR_LOONGARCH64: Self =
+        Self::new(0xE611C702, 0x575C, 0x4CBE, 0x9A46434FA0BF7E3F);
+    /// Linux `/usr` partition for RISC-V 32.
+    pub const LINUX_USR_RISCV32: Self = Self::new(0xB933FB22, 0x5C3F, 0x4F91, 0xAF90E2BB0FA50702);
+    /// Linux `/usr` partition for RISC-V 64.
+    pub const LINUX_USR_RISCV64: Self = Self::new(0xBEAEC34B, 0x8442, 0x439B, 0xA40B984381ED097D);
+    /// Linux dm-verity hash partition of the root partition for x86.
+    pub const LINUX_ROOT_VERITY_X86: Self =
+        Self::new(0xD13C5D3B, 0xB5D1, 0x422A, 0xB29F9454FDC89D76);
+    /// Linux dm-verity hash partition of the root partition for x86-64.
+    pub const LINUX_ROOT_VERITY_X86_64: Self =
+        Self::new(0x2C7357ED, 0xEBD2, 0x46D9, 0xAEC123D437EC2BF5);
+    /// Linux dm-verity hash partition of the root partition for ARM.
+    pub const LINUX_ROOT_VERITY_ARM: Self =
+        Self::new(0x7386CDF2, 0x203C, 0x47A9, 0xA498F2ECCE45A2D6);
+    /// Linux dm-verity hash partition of the root partition for ARM64.
+    pub const LINUX_ROOT_VERITY_ARM64: Self =
+        Self::new(0xDF3300CE, 0xD69F, 0x4C92, 0x978C9BFB0F38D820);
+    /// Linux dm-verity hash partition of the root partition for IA-64.
+    pub const LINUX_ROOT_VERITY_IA64: Self =
+        Self::new(0x86ED10D5, 0xB607, 0x45BB, 0x8957D350F23D0571);
+    /// Linux dm-verity hash partition of the root partition for LoongArch64.
+    pub const LINUX_ROOT_VERITY_LOONGARCH64: Self =
+        Self::new(0xF3393B22, 0xE9AF, 0x4613, 0xA9489D3BFBD0C535);
+    /// Linux dm-verity hash partition of the root partition for RISC-V 32.
+    pub const LINUX_ROOT_VERITY_RISCV32: Self =
+        Self::new(0xAE0253BE, 0x1167, 0x4007, 0xAC6843926C14C5DE);
+    /// Linux dm-verity hash partition of the root partition for RISC-V 64.
+    pub const LINUX_ROOT_VERITY_RISCV64: Self =
+        Self::new(0xB6ED5582, 0x440B, 0x4209, 0xB8DA5FF7C419EA3D);
+    /// Linux dm-verity hash partition of the `/usr` partition for x86.
+    pub const LINUX_USR_VERITY_X86: Self =
+        Self::new(0x8F461B0D, 0x14EE, 0x4E81, 0x9AA9049B6FB97ABD);
+    /// Linux dm-verity hash partition of the `/usr` partition for x86-64.
+    pub const LINUX_USR_VERITY_X86_64: Self =
+        Self::new(0x77FF5F63, 0xE7B6, 0x4633, 0xACF41565B864C0E6);
+    /// Linux dm-verity hash partition of the `/usr` partition for ARM.
+    pub const LINUX_USR_VERITY_ARM: Self =
+        Self::new(0xC215D751, 0x7BCD, 0x4649, 0xBE906627490A4C05);
+    /// Linux dm-verity hash partition of the `/usr` partition for ARM64.
+    pub const LINUX_USR_VERITY_ARM64: Self =
+        Self::new(0x6E11A4E7, 0xFBCA, 0x4DED, 0xB9E9E1A512BB664E);
+    /// Linux dm-verity hash partition of the `/usr` partition for IA-64.
+    pub const LINUX_USR_VERITY_IA64: Self =
+        Self::new(0x6A491E03, 0x3BE7, 0x4545, 0x8E3883320E0EA880);
+    /// Linux dm-verity hash partition of the `/usr` partition for LoongArch64.
+    pub const LINUX_USR_VERITY_LOONGARCH64: Self =
+        Self::new(0xF46B2C26, 0x59AE, 0x48F0, 0x9106C50ED47F673D);
+    /// Linux dm-verity hash partition of the `/usr` partition for RISC-V 32.
+    pub const LINUX_USR_VERITY_RISCV32: Self =
+        Self::new(0xCB1EE4E3, 0x8CD0, 0x4136, 0xA0A4AA61A32E8730);
+    /// Linux dm-verity hash partition of the `/usr` partition for RISC-V 64.
+    pub const LINUX_USR_VERITY_RISCV64: Self =
+        Self::new(0x8F1056BE, 0x9B05, 0x47C4, 0x81D6BE53128E5B54);
 }
 
 impl TryFrom<&[u8]> for GUID {
@@ -4,8 +4,12 @@ use crate::{read_le_bytes, GPTError, Result, GUID};
 
 const EFI_SIGNATURE: u64 = 0x5452415020494645;
 const GPT_REV: u32 = 0x00010000;
+/// Size in bytes of the GPT Header defined by the UEFI specification.
+pub(crate) const HEADER_SIZE: u32 = 92;
+/// Biggest [`GPTHeader::size`] supported. Bigger headers are rejected by [`GPTHeader::parse`].
+pub const MAX_HEADER_SIZE: u32 = 512;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GPTHeader {
     /// Size in bytes of the GPT Header. The [`Self::size`] must be greater than or equal to
     /// 92 and must be less than or equal to the logical block size.
@@ -42,11 +46,17 @@ pub struct GPTHeader {
     /// The CRC32 of the GUID Partition Entry array. Starts at [`Self::p_entry_lba`] and is
     /// computed over a byte length of [`Self::num_parts`] * [`Self::size_of_p_entry`].
     pub p_crc32: u32,
+
+    /// The bytes following the 92 bytes defined by the UEFI specification, up to
+    /// [`Self::size`]. They are reserved and should be zero, but are covered by [`Self::crc32`]
+    /// and written back as they were read.
+    pub reserved: [u8; (MAX_HEADER_SIZE - HEADER_SIZE) as usize],
 }
 
 impl GPTHeader {
     /// Read the GPT header from buf and serializes it into this struct.
-    /// Checks for `Signature` and `Revision`, but nothing else.
+    /// Checks for `Signature` and `Revision`, and that [`Self::size`] is between 92 and
+    /// [`MAX_HEADER_SIZE`] bytes, but nothing else.
     pub fn parse(buf: &[u8]) -> Result<Self> {
         let sig = read_le_bytes!(buf, u64, 0..8);
         if sig != EFI_SIGNATURE {
@@ -59,6 +69,16 @@ impl GPTHeader {
         }
 
         let size = read_le_bytes!(buf, u32, 12..16);
+        if !(HEADER_SIZE..=MAX_HEADER_SIZE).contains(&size) {
+            return Err(GPTError::InvalidData);
+        }
+        let reserved_len = (size - HEADER_SIZE) as usize;
+        let mut reserved = [0u8; (MAX_HEADER_SIZE - HEADER_SIZE) as usize];
+        reserved[..reserved_len].copy_from_slice(
+            buf.get(HEADER_SIZE as usize..size as usize)
+                .ok_or(GPTError::UnexpectedEOF)?,
+        );
+
         let crc32 = read_le_bytes!(buf, u32, 16..20);
 
         let my_lba = read_le_bytes!(buf, u64, 24..32);
@@ -92,9 +112,41 @@ impl GPTHeader {
             num_parts,
             size_of_p_entry,
             p_crc32,
+
+            reserved,
         })
     }
 
+    /// Create the header for the other copy of the partition table, with its partition entry
+    /// array at `p_entry_lba`. Swaps [`Self::my_lba`] and [`Self::other_lba`] and recalculates the
+    /// crc.
+    pub fn alternate(&self, p_entry_lba: u64) -> Self {
+        let mut header = Self {
+            my_lba: self.other_lba,
+            other_lba: self.my_lba,
+            p_entry_lba,
+            ..*self
+        };
+        header.crc32 = header.calculate_crc();
+
+        header
+    }
+
+    /// The usual start of the partition entry array of the alternate header: Directly after the
+    /// primary header, or directly before the backup header.
+    pub fn alternate_p_entry_lba(&self, block_size: u32) -> Result<u64> {
+        if self.my_lba > self.other_lba {
+            return Ok(self.other_lba + 1);
+        }
+
+        let p_table_size = self.size_of_p_entry as u64 * self.num_parts as u64;
+        let blocks = crate::ceil64(p_table_size, block_size as u64);
+
+        self.other_lba
+            .checked_sub(blocks)
+            .ok_or(GPTError::InvalidLba(self.other_lba))
+    }
+
     /// Check this header for valid data. needs the bits of the partition table as input.
     pub fn validate(&self, my_lba: u64, part_table: &[u8]) -> Result<()> {
         if self.my_lba != my_lba {
@@ -106,7 +158,39 @@ impl GPTHeader {
         Ok(())
     }
 
+    /// Check that the header and partition entry array, and the usable blocks lie on a device
+    /// with `num_blocks` logical blocks of `block_size` bytes.
+    pub fn validate_lbas(&self, num_blocks: u64, block_size: u32) -> Result<()> {
+        for lba in [self.my_lba, self.other_lba, self.last_lba] {
+            if lba >= num_blocks {
+                return Err(GPTError::InvalidLba(lba));
+            }
+        }
+
+        if self.first_lba > self.last_lba {
+            return Err(GPTError::InvalidLba(self.first_lba));
+        }
+
+        let p_table_size = self.size_of_p_entry as u64 * self.num_parts as u64;
+        let blocks = crate::ceil64(p_table_size, block_size as u64);
+        if self.p_entry_lba.saturating_add(blocks) > num_blocks {
+            return Err(GPTError::InvalidLba(self.p_entry_lba));
+        }
+
+        Ok(())
+    }
+
     pub fn validate_part_crc(&self, part_table: &[u8]) -> Result<()> {
+        let digest = self.calculate_part_crc(part_table)?;
+        if digest != self.p_crc32 {
+            return Err(GPTError::InvalidCrcParts(digest, self.p_crc32));
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the crc32 of the partition entry array described by this header.
+    pub fn calculate_part_crc(&self, part_table: &[u8]) -> Result<u32> {
         let len = (self.num_parts * self.size_of_p_entry) as usize;
         if len > part_table.len() {
             return Err(GPTError::PartitionTableToShort(len as u32));
@@ -115,10 +199,14 @@ impl GPTHeader {
         let mut digest = crc32::Digest::new(crc32::IEEE);
         digest.write(&part_table[0..len]);
 
-        let digest = digest.sum32();
-        if digest != self.p_crc32 {
-            return Err(GPTError::InvalidCrcParts(digest, self.p_crc32));
-        }
+        Ok(digest.sum32())
+    }
+
+    /// Recompute [`Self::p_crc32`] over a modified partition entry array, and with it the
+    /// [`Self::crc32`] of this header.
+    pub fn update_part_crc(&mut self, part_table: &[u8]) -> Result<()> {
+        self.p_crc32 = self.calculate_part_crc(part_table)?;
+        self.crc32 = self.calculate_crc();
 
         Ok(())
     }
@@ -133,6 +221,71 @@ impl GPTHeader {
         Ok(())
     }
 
+    /// Serialize the [`Self::size`] bytes of this header into `buf`, as they are laid out on
+    /// disk, including the [`Self::reserved`] bytes.
+    ///
+    /// The crc32 is recomputed instead of using [`Self::crc32`]. The rest of `buf` is not
+    /// touched.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<()> {
+        let size = self.size as usize;
+        if !(HEADER_SIZE..=MAX_HEADER_SIZE).contains(&self.size) {
+            return Err(GPTError::InvalidData);
+        }
+        if buf.len() < size {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        self.write_fields(buf);
+        buf[16..20].copy_from_slice(&self.calculate_crc().to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Serialize this header with a recomputed crc32. Only the first [`Self::size`] bytes
+    /// belong to the header, the rest is zero.
+    pub fn to_bytes(&self) -> [u8; MAX_HEADER_SIZE as usize] {
+        let mut buf = [0u8; MAX_HEADER_SIZE as usize];
+        self.write_fields(&mut buf);
+        buf[16..20].copy_from_slice(&self.calculate_crc().to_le_bytes());
+
+        buf
+    }
+
+    /// Write the [`Self::size`] bytes of this header into buf, without touching
+    /// [`Self::crc32`]. The size has to be checked by the caller.
+    fn write_fields(&self, buf: &mut [u8]) {
+        buf[0..8].copy_from_slice(&EFI_SIGNATURE.to_le_bytes());
+        buf[8..12].copy_from_slice(&GPT_REV.to_le_bytes());
+        buf[12..16].copy_from_slice(&self.size.to_le_bytes());
+        buf[16..20].copy_from_slice(&self.crc32.to_le_bytes());
+        buf[20..24].copy_from_slice(&[0, 0, 0, 0]);
+
+        buf[24..32].copy_from_slice(&self.my_lba.to_le_bytes());
+        buf[32..40].copy_from_slice(&self.other_lba.to_le_bytes());
+
+        buf[40..48].copy_from_slice(&self.first_lba.to_le_bytes());
+        buf[48..56].copy_from_slice(&self.last_lba.to_le_bytes());
+
+        buf[56..72].copy_from_slice(&self.guid.as_bytes());
+
+        buf[72..80].copy_from_slice(&self.p_entry_lba.to_le_bytes());
+
+        buf[80..84].copy_from_slice(&self.num_parts.to_le_bytes());
+        buf[84..88].copy_from_slice(&self.size_of_p_entry.to_le_bytes());
+        buf[88..92].copy_from_slice(&self.p_crc32.to_le_bytes());
+
+        buf[HEADER_SIZE as usize..self.size as usize].copy_from_slice(self.reserved_bytes());
+    }
+
+    /// The [`Self::reserved`] bytes which are part of the header. Empty for an invalid
+    /// [`Self::size`].
+    fn reserved_bytes(&self) -> &[u8] {
+        let len = self.size.saturating_sub(HEADER_SIZE) as usize;
+        self.reserved.get(..len).unwrap_or(&[])
+    }
+
+    /// Calculate the crc32 of the [`Self::size`] bytes of this header, with [`Self::crc32`] set
+    /// to 0.
     pub fn calculate_crc(&self) -> u32 {
         let mut digest = crc32::Digest::new(crc32::IEEE);
 
@@ -149,6 +302,7 @@ impl GPTHeader {
         digest.write(&self.num_parts.to_le_bytes());
         digest.write(&self.size_of_p_entry.to_le_bytes());
         digest.write(&self.p_crc32.to_le_bytes());
+        digest.write(self.reserved_bytes());
 
         digest.sum32()
     }
@@ -168,3 +322,64 @@ impl core::fmt::Display for GptHeaderType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{GPTHeader, HEADER_SIZE, MAX_HEADER_SIZE};
+    use crate::GUID;
+
+    fn test_header(size: u32) -> GPTHeader {
+        GPTHeader {
+            size,
+            crc32: 0,
+            my_lba: 1,
+            other_lba: 95,
+            first_lba: 34,
+            last_lba: 62,
+            guid: GUID::ESP,
+            p_entry_lba: 2,
+            num_parts: 128,
+            size_of_p_entry: 128,
+            p_crc32: 0x090e9ba6,
+            reserved: [0; (MAX_HEADER_SIZE - HEADER_SIZE) as usize],
+        }
+    }
+
+    #[test]
+    fn to_bytes_parse() {
+        let header = test_header(HEADER_SIZE);
+        let buf = header.to_bytes();
+
+        let parsed = GPTHeader::parse(&buf).unwrap();
+        assert_eq!(parsed.crc32, header.calculate_crc());
+        parsed.validate_crc().unwrap();
+        assert_eq!(parsed.to_bytes(), buf);
+        assert_eq!(buf[92..], [0u8; 420][..]);
+    }
+
+    #[test]
+    fn write_to_keeps_trailing_bytes() {
+        let mut header = test_header(100);
+        header.reserved[..8].copy_from_slice(&[0xa5; 8]);
+        let mut buf = [0x5au8; 512];
+
+        header.write_to(&mut buf).unwrap();
+        assert_eq!(buf[92..100], [0xa5u8; 8]);
+        assert_eq!(buf[100..], [0x5au8; 412][..]);
+
+        // The crc covers the trailing bytes, and they are kept when writing into a fresh block.
+        let parsed = GPTHeader::parse(&buf).unwrap();
+        parsed.validate_crc().unwrap();
+        assert_eq!(parsed.crc32, header.calculate_crc());
+        assert_ne!(parsed.crc32, test_header(100).calculate_crc());
+
+        let mut rewritten = [0u8; 512];
+        parsed.write_to(&mut rewritten).unwrap();
+        assert_eq!(rewritten[..100], buf[..100]);
+        assert_eq!(parsed.to_bytes()[..100], buf[..100]);
+
+        assert!(header.write_to(&mut [0u8; 96]).is_err());
+        assert!(test_header(64).write_to(&mut buf).is_err());
+        assert!(GPTHeader::parse(&buf[..96]).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use block_device::BlockDevice;
+
+use crate::mbr::{MBRPartitionRecord, MasterBootRecord, MbrType};
+use crate::part::RawPartHeader;
+use crate::{GPTError, Result, GPT, GUID};
+
+/// GPT partition to mirror in a hybrid MBR, used by [`GPT::create_hybrid_mbr`].
+#[derive(Debug, Clone, Copy)]
+pub struct HybridPartition {
+    idx: u32,
+    os_indicator: u8,
+    boot_indicator: u8,
+}
+
+impl HybridPartition {
+    /// Boot indicator of a bootable partition.
+    pub const BOOTABLE: u8 = 0x80;
+
+    /// Mirror the GPT partition at `idx` with the MBR partition type `os_indicator`.
+    pub fn new(idx: u32, os_indicator: u8) -> Self {
+        Self {
+            idx,
+            os_indicator,
+            boot_indicator: 0,
+        }
+    }
+
+    /// Mark the partition as bootable for legacy BIOS.
+    pub fn bootable(mut self, bootable: bool) -> Self {
+        self.boot_indicator = if bootable { Self::BOOTABLE } else { 0 };
+        self
+    }
+}
+
+/// Hybrid MBRs, which list some GPT partitions in the MBR as well, for legacy BIOS boot.
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Kind of MBR in front of the partition table.
+    pub fn mbr_type(&self) -> MbrType {
+        self.mbr_type
+    }
+
+    /// Partition records of the MBR, including the protective partition.
+    pub fn mbr_partitions(&self) -> &[MBRPartitionRecord; 4] {
+        &self.mbr_partition
+    }
+
+    /// Iterate over the partitions of a hybrid MBR, together with their slot in the MBR. The
+    /// protective partition and empty records are skipped.
+    pub fn hybrid_partitions(&self) -> impl Iterator<Item = (usize, &MBRPartitionRecord)> {
+        hybrid_partitions(&self.mbr_partition)
+    }
+
+    /// Check that every partition of the hybrid MBR covers the same blocks as a GPT partition.
+    /// Fails with [`GPTError::InvalidMbr`] otherwise. The MBR partition types are not checked.
+    pub fn validate_hybrid_mbr(&self) -> Result<()> {
+        let mirrored = self.match_hybrid(self.used_partitions::<GUID, u64>()?)?;
+        self.check_mirrored(&mirrored)
+    }
+
+    /// Like [`Self::validate_hybrid_mbr`], but reads the partition entry array in chunks into
+    /// `buf`. `buf` has to hold at least one block.
+    pub fn validate_hybrid_mbr_with_buf(&self, buf: &mut [u8]) -> Result<()> {
+        let mirrored = self.match_hybrid(self.used_partitions_with_buf::<GUID, u64>(buf))?;
+        self.check_mirrored(&mirrored)
+    }
+
+    /// Write a hybrid MBR mirroring up to three GPT partitions, for disks which have to boot
+    /// with legacy BIOS as well.
+    ///
+    /// The protective partition is put in the first slot, covering the primary GPT header and
+    /// partition entry array. The partitions follow in the order of `parts`, with CHS addresses
+    /// computed from their LBAs. The boot code and disk signature of the MBR are kept. `buf` is
+    /// used as scratch space of at least one block.
+    ///
+    /// GPT partitions have to end below 2 TiB (with 512 byte blocks) to fit into the MBR. The
+    /// partitions are read as written to the device, so changes to them have to be written by
+    /// [`Self::commit`] first. Fails with [`GPTError::StagedChanges`] otherwise.
+    pub fn create_hybrid_mbr(&mut self, parts: &[HybridPartition], buf: &mut [u8]) -> Result<()> {
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(GPTError::InvalidData);
+        }
+        self.check_committed()?;
+
+        let first_lba = self.header.first_lba;
+        let protective_size = first_lba
+            .checked_sub(1)
+            .and_then(|size| u32::try_from(size).ok())
+            .ok_or(GPTError::InvalidLba(first_lba))?;
+
+        let mut partition = [MBRPartitionRecord::EMPTY; 4];
+        let mut mirrored = [None; 4];
+        partition[0] = MBRPartitionRecord::new(
+            MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE,
+            1,
+            protective_size,
+        );
+
+        for (slot, part) in parts.iter().enumerate() {
+            let entry = self.get_partition_with_buf::<GUID, u64>(part.idx, buf)?;
+            let (starting_lba, size_in_lba) = mbr_lbas(&entry)?;
+
+            let mut record = MBRPartitionRecord::new(part.os_indicator, starting_lba, size_in_lba);
+            record.boot_indicator = part.boot_indicator;
+            partition[slot + 1] = record;
+            mirrored[slot + 1] = Some(part.idx);
+        }
+
+        self.write_mbr(partition, mirrored, buf)
+    }
+
+    /// Update the partitions of a hybrid MBR after the GPT partitions they mirror were changed.
+    ///
+    /// Partitions follow the new LBAs of their GPT partition, and are removed if it was removed.
+    /// Without any partitions left, a protective MBR covering the whole disk is written.
+    /// Partitions which didn't match a GPT partition when opening the table are kept as they
+    /// are. `buf` is used as scratch space of at least one block.
+    ///
+    /// Like [`Self::create_hybrid_mbr`], the partitions are read as written to the device, so it
+    /// fails with [`GPTError::StagedChanges`] if changes are not yet written by [`Self::commit`].
+    pub fn sync_hybrid_mbr(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.check_committed()?;
+
+        let mut partition = self.mbr_partition;
+        let mut mirrored = self.mbr_hybrid;
+
+        for (record, mirrored) in partition.iter_mut().zip(mirrored.iter_mut()) {
+            let idx = match *mirrored {
+                Some(idx) => idx,
+                None => continue,
+            };
+
+            let entry = self.get_partition_with_buf::<GUID, u64>(idx, buf)?;
+            if entry.type_guid == GUID::UNUSED {
+                *record = MBRPartitionRecord::EMPTY;
+                *mirrored = None;
+                continue;
+            }
+
+            let (starting_lba, size_in_lba) = mbr_lbas(&entry)?;
+            record.set_lbas(starting_lba, size_in_lba);
+        }
+
+        if hybrid_partitions(&partition).next().is_none() {
+            let last_lba = core::cmp::max(self.header.my_lba, self.header.other_lba);
+            partition = MasterBootRecord::new_protective(last_lba + 1).partition;
+        }
+
+        self.write_mbr(partition, mirrored, buf)
+    }
+
+    /// Find out if the MBR is a hybrid MBR, and which GPT partitions it mirrors.
+    pub(crate) fn detect_mbr_type(&mut self, buf: &mut [u8]) -> Result<()> {
+        // Don't read the partition entry array for protective MBRs.
+        if self.hybrid_partitions().next().is_none() {
+            self.mbr_type = MbrType::Protective;
+            return Ok(());
+        }
+
+        // Uses the partition entry array cached by `open`, and only reads it otherwise.
+        let mirrored = self.match_hybrid(self.used_partitions_with_buf::<GUID, u64>(buf))?;
+        self.mbr_type = self.mbr_type_of(&mirrored);
+        self.mbr_hybrid = mirrored;
+
+        Ok(())
+    }
+
+    /// Find the GPT partition covering the same blocks as each hybrid partition.
+    ///
+    /// Partitions are matched by their LBA range only. The MBR partition type is not checked
+    /// against the GPT partition type, as there is no fixed mapping between them.
+    fn match_hybrid<I>(&self, parts: I) -> Result<[Option<u32>; 4]>
+    where
+        I: Iterator<Item = Result<(u32, RawPartHeader)>>,
+    {
+        let mut mirrored = [None; 4];
+        for part in parts {
+            let (idx, part) = part?;
+            for (slot, record) in self.hybrid_partitions() {
+                if record.starting_lba() as u64 == part.start_lba
+                    && record.ending_lba() as u64 == part.end_lba + 1
+                {
+                    mirrored[slot] = Some(idx);
+                }
+            }
+        }
+
+        Ok(mirrored)
+    }
+
+    /// Fail if partition entries are staged, which would not be seen by the `*_with_buf` lookups.
+    fn check_committed(&self) -> Result<()> {
+        #[cfg(feature = "alloc")]
+        if self.has_staged_changes() {
+            return Err(GPTError::StagedChanges);
+        }
+
+        Ok(())
+    }
+
+    fn check_mirrored(&self, mirrored: &[Option<u32>; 4]) -> Result<()> {
+        match self.mbr_type_of(mirrored) {
+            MbrType::HybridMismatch => Err(GPTError::InvalidMbr),
+            _ => Ok(()),
+        }
+    }
+
+    fn mbr_type_of(&self, mirrored: &[Option<u32>; 4]) -> MbrType {
+        let mut hybrid = self.hybrid_partitions().peekable();
+        if hybrid.peek().is_none() {
+            return MbrType::Protective;
+        }
+
+        if hybrid.all(|(slot, _)| mirrored[slot].is_some()) {
+            MbrType::Hybrid
+        } else {
+            MbrType::HybridMismatch
+        }
+    }
+
+    /// Replace the partition records of the MBR on the device, keeping its boot code.
+    fn write_mbr(
+        &mut self,
+        partition: [MBRPartitionRecord; 4],
+        mirrored: [Option<u32>; 4],
+        buf: &mut [u8],
+    ) -> Result<()> {
+        let block_size = T::BLOCK_SIZE as usize;
+        let buf = buf
+            .get_mut(..core::cmp::max(block_size, MasterBootRecord::SIZE))
+            .ok_or(GPTError::UnexpectedEOF)?;
+
+        self.block.read(&mut buf[..block_size], 0, 1)?;
+        let mut mbr = MasterBootRecord::parse(buf)?;
+        mbr.partition = partition;
+        mbr.signature = MasterBootRecord::SIGNATURE.to_le_bytes();
+        mbr.verify_partitions(None)?;
+
+        mbr.write_to(buf)?;
+        self.block.write(&buf[..block_size], 0, 1)?;
+
+        self.mbr_partition = partition;
+        self.mbr_hybrid = mirrored;
+        self.mbr_type = self.mbr_type_of(&mirrored);
+
+        Ok(())
+    }
+}
+
+fn hybrid_partitions(
+    partition: &[MBRPartitionRecord; 4],
+) -> impl Iterator<Item = (usize, &MBRPartitionRecord)> {
+    partition.iter().enumerate().filter(|(_, part)| {
+        !part.is_empty() && part.os_indicator != MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE
+    })
+}
+
+/// Start and size of the used GPT partition `entry`, as they are stored in the MBR.
+fn mbr_lbas(entry: &RawPartHeader) -> Result<(u32, u32)> {
+    if entry.type_guid == GUID::UNUSED {
+        return Err(GPTError::InvalidData);
+    }
+
+    let starting_lba =
+        u32::try_from(entry.start_lba).map_err(|_| GPTError::InvalidLba(entry.start_lba))?;
+    let size_in_lba = entry
+        .end_lba
+        .checked_sub(entry.start_lba)
+        .and_then(|size| u32::try_from(size + 1).ok())
+        .ok_or(GPTError::InvalidLba(entry.end_lba))?;
+    if starting_lba.checked_add(size_in_lba).is_none() {
+        return Err(GPTError::InvalidLba(entry.end_lba));
+    }
+
+    Ok((starting_lba, size_in_lba))
+}
+
+#[cfg(test)]
+mod test {
+    use super::mbr_lbas;
+    use crate::part::RawPartHeader;
+    use crate::{GPTError, GUID};
+
+    #[test]
+    fn lbas() {
+        let entry = |start_lba, end_lba| {
+            RawPartHeader::new(GUID::LINUX_FS, GUID::UNUSED, start_lba, end_lba, 0)
+        };
+
+        assert_eq!(mbr_lbas(&entry(34, 62)).unwrap(), (34, 29));
+        assert!(matches!(
+            mbr_lbas(&entry(62, 34)),
+            Err(GPTError::InvalidLba(34))
+        ));
+        assert!(matches!(
+            mbr_lbas(&entry(34, u32::MAX as u64)),
+            Err(GPTError::InvalidLba(_))
+        ));
+    }
+}
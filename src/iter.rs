@@ -0,0 +1,273 @@
+use core::marker::PhantomData;
+
+use block_device::BlockDevice;
+
+use crate::part::{GPTPartHeader, GPTTypeGuid, RawPartHeader};
+use crate::{ceil64, read_buf, GPTError, Result, GPT};
+
+#[cfg(feature = "alloc")]
+type TableBuf = alloc::vec::Vec<u8>;
+#[cfg(not(feature = "alloc"))]
+type TableBuf = [u8; crate::DEFAULT_PARTTABLE_SIZE as usize];
+
+/// Check if the type guid of the raw partition entry `buf` marks it as unused.
+fn is_unused_entry(buf: &[u8]) -> bool {
+    buf[..16].iter().all(|&b| b == 0)
+}
+
+/// Iterator over the partition entries of a [`GPT`], returned by [`GPT::partitions`] and
+/// [`GPT::used_partitions`].
+///
+/// The partition entry array is copied from the cache of the [`GPT`], or read once when the
+/// iterator is created. Yields the index of the
+/// entry together with the parsed entry, and stops after the first error.
+pub struct Partitions<PT = crate::part::DefaultGPTTypeGuid, PA = crate::part::Attributes> {
+    buf: TableBuf,
+    idx: u32,
+    num_parts: u32,
+    size_of_p_entry: u32,
+    skip_unused: bool,
+    _types: PhantomData<(PT, PA)>,
+}
+
+impl<PT, PA> Iterator for Partitions<PT, PA>
+where
+    PT: GPTTypeGuid,
+    GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+    PA: TryFrom<u64>,
+    GPTError: From<<PA as TryFrom<u64>>::Error>,
+{
+    type Item = Result<(u32, GPTPartHeader<PT, PA>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.num_parts {
+            let idx = self.idx;
+            self.idx += 1;
+
+            let offset = idx as usize * self.size_of_p_entry as usize;
+            let entry = &self.buf[offset..];
+            if self.skip_unused && is_unused_entry(entry) {
+                continue;
+            }
+
+            return Some(match GPTPartHeader::parse(entry) {
+                Ok(part) => Ok((idx, part)),
+                Err(e) => {
+                    self.idx = self.num_parts;
+                    Err(e)
+                }
+            });
+        }
+
+        None
+    }
+}
+
+/// Iterator over the partition entries of a [`GPT`] without allocations, returned by
+/// [`GPT::partitions_with_buf`] and [`GPT::used_partitions_with_buf`].
+///
+/// Reads the partition entry array in chunks of as many blocks as fit into the buffer passed by
+/// the caller, so the buffer only has to hold a single block. If the [`GPT`] caches the
+/// partition entry array, the entries are taken from the cache instead, so the same entries as
+/// of [`GPT::partitions`] are returned. Yields the index of the entry together with the parsed
+/// entry, and stops after the first error.
+pub struct PartitionsBuf<'a, T, PT = crate::part::DefaultGPTTypeGuid, PA = crate::part::Attributes>
+{
+    gpt: &'a GPT<T>,
+    buf: &'a mut [u8],
+    /// First block and number of blocks currently held in `buf`.
+    loaded: Option<(u64, u64)>,
+    idx: u32,
+    skip_unused: bool,
+    _types: PhantomData<(PT, PA)>,
+}
+
+impl<'a, T, PT, PA> PartitionsBuf<'a, T, PT, PA>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Read the blocks holding the entry at `idx`, and return its offset in `buf`.
+    ///
+    /// As many blocks of the partition entry array as fit into `buf` are read at once.
+    fn load(&mut self, idx: u32) -> Result<usize> {
+        let block_size = T::BLOCK_SIZE as u64;
+        let header = &self.gpt.header;
+
+        let offset = idx as u64 * header.size_of_p_entry as u64;
+        let lba = offset / block_size;
+        let in_block = offset % block_size;
+        let needed = ceil64(in_block + RawPartHeader::SIZE as u64, block_size);
+
+        if let Some((start, count)) = self.loaded {
+            if lba >= start && lba + needed <= start + count {
+                return Ok(((lba - start) * block_size + in_block) as usize);
+            }
+        }
+
+        let p_table_size = header.num_parts as u64 * header.size_of_p_entry as u64;
+        let table_blocks = ceil64(p_table_size, block_size);
+        let fit = self.buf.len() as u64 / block_size;
+        let count = core::cmp::max(needed, core::cmp::min(fit, table_blocks - lba));
+
+        let buf = self
+            .buf
+            .get_mut(..(count * block_size) as usize)
+            .ok_or(GPTError::UnexpectedEOF)?;
+        self.loaded = None;
+        self.gpt
+            .block
+            .read(buf, (header.p_entry_lba + lba) as usize, count as usize)?;
+        self.loaded = Some((lba, count));
+
+        Ok(in_block as usize)
+    }
+
+    /// The raw entry at `idx`, out of the cache of the [`GPT`] or read into `buf`.
+    fn entry(&mut self, idx: u32) -> Result<&[u8]> {
+        #[cfg(feature = "alloc")]
+        {
+            let gpt = self.gpt;
+            if let Some(cache) = &gpt.cache {
+                let offset = idx as usize * gpt.header.size_of_p_entry as usize;
+                return Ok(&cache[offset..offset + RawPartHeader::SIZE]);
+            }
+        }
+
+        let offset = self.load(idx)?;
+        Ok(&self.buf[offset..offset + RawPartHeader::SIZE])
+    }
+}
+
+impl<'a, T, PT, PA> Iterator for PartitionsBuf<'a, T, PT, PA>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+    PT: GPTTypeGuid,
+    GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+    PA: TryFrom<u64>,
+    GPTError: From<<PA as TryFrom<u64>>::Error>,
+{
+    type Item = Result<(u32, GPTPartHeader<PT, PA>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.gpt.header.num_parts {
+            let idx = self.idx;
+            self.idx += 1;
+
+            let skip_unused = self.skip_unused;
+            let entry = match self.entry(idx) {
+                Ok(entry) if skip_unused && is_unused_entry(entry) => continue,
+                Ok(entry) => GPTPartHeader::parse(entry).map(|part| (idx, part)),
+                Err(e) => Err(e),
+            };
+            if entry.is_err() {
+                self.idx = self.gpt.header.num_parts;
+            }
+
+            return Some(entry);
+        }
+
+        None
+    }
+}
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Iterate over all entries of the partition entry array, including unused ones.
+    ///
+    /// The partition entry array is copied from memory, or read once if it is not cached. Without
+    /// the `alloc` feature it has to fit into [`crate::DEFAULT_PARTTABLE_SIZE`] bytes, see
+    /// [`Self::partitions_with_buf`] otherwise.
+    pub fn partitions<PT, PA>(&self) -> Result<Partitions<PT, PA>> {
+        Ok(Partitions {
+            buf: self.table_buf()?,
+            idx: 0,
+            num_parts: self.header.num_parts,
+            size_of_p_entry: self.header.size_of_p_entry,
+            skip_unused: false,
+            _types: PhantomData,
+        })
+    }
+
+    /// Copy of the cached partition entry array, or the one read from the device.
+    fn table_buf(&self) -> Result<TableBuf> {
+        #[cfg(feature = "alloc")]
+        if let Some(cache) = &self.cache {
+            let mut buf = alloc::vec::Vec::new();
+            buf.try_reserve_exact(cache.len())?; // Catch allocation errors
+            buf.extend_from_slice(cache);
+            return Ok(buf);
+        }
+
+        let p_table_size = self.header.size_of_p_entry as usize * self.header.num_parts as usize;
+        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+
+        read_buf(
+            self.header.p_entry_lba as usize,
+            p_table_size,
+            &self.block,
+            blocks,
+        )
+    }
+
+    /// Iterate over the used entries of the partition entry array, skipping entries with an
+    /// unused type guid.
+    pub fn used_partitions<PT, PA>(&self) -> Result<Partitions<PT, PA>> {
+        let mut partitions = self.partitions()?;
+        partitions.skip_unused = true;
+        Ok(partitions)
+    }
+
+    /// Iterate over all entries of the partition entry array, reading it in chunks into `buf`.
+    /// `buf` has to hold at least one block, and is not used if the partition entry array is
+    /// cached.
+    pub fn partitions_with_buf<'a, PT, PA>(
+        &'a self,
+        buf: &'a mut [u8],
+    ) -> PartitionsBuf<'a, T, PT, PA> {
+        PartitionsBuf {
+            gpt: self,
+            buf,
+            loaded: None,
+            idx: 0,
+            skip_unused: false,
+            _types: PhantomData,
+        }
+    }
+
+    /// Like [`Self::get_partition`], but reads only the blocks holding the entry into `buf`.
+    /// `buf` has to hold at least one block.
+    pub fn get_partition_with_buf<PT, PA>(
+        &self,
+        idx: u32,
+        buf: &mut [u8],
+    ) -> Result<GPTPartHeader<PT, PA>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        let mut partitions = self.partitions_with_buf(buf);
+        partitions.idx = idx;
+        match partitions.next() {
+            Some(part) => part.map(|(_, part)| part),
+            None => Err(GPTError::InvalidData),
+        }
+    }
+
+    /// Iterate over the used entries of the partition entry array, reading it in chunks into
+    /// `buf`. `buf` has to hold at least one block.
+    pub fn used_partitions_with_buf<'a, PT, PA>(
+        &'a self,
+        buf: &'a mut [u8],
+    ) -> PartitionsBuf<'a, T, PT, PA> {
+        let mut partitions = self.partitions_with_buf(buf);
+        partitions.skip_unused = true;
+        partitions
+    }
+}
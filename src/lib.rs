@@ -1,5 +1,8 @@
 #![cfg_attr(not(any(feature = "std", test, doc)), no_std)]
 #![deny(unsafe_op_in_unsafe_fn)]
+// GPTParseError contains the GPT, to allow repairing broken headers. The lint is unknown to the
+// clippy of the minimal supported rust version.
+#![allow(unknown_lints, clippy::result_large_err)]
 
 #[cfg(any(feature = "alloc", doc))]
 extern crate alloc;
@@ -10,11 +13,15 @@ use alloc::vec::Vec;
 extern crate core;
 
 use block_device::BlockDevice;
+use crc::{crc32, Hasher32};
 
 pub use crate::error::{GPTError, GPTParseError, GptRepair, Result};
 use crate::header::{GPTHeader, GptHeaderType};
 
 pub const DEFAULT_PARTTABLE_SIZE: u32 = 16384;
+/// Size of the stack buffer [`GPT::get_partition`] reads the blocks holding an entry into,
+/// without the `alloc` feature.
+pub const ENTRY_BUF_SIZE: usize = 4096;
 //pub const DEFAULT_PARTTABLE_BLOCKS: u32 = DEFAULT_PARTTABLE_SIZE / BLOCK_SIZE;
 
 macro_rules! read_le_bytes {
@@ -29,8 +36,19 @@ macro_rules! read_le_bytes {
 }
 pub(crate) use read_le_bytes; // trick to export to crate
 
+mod block;
+mod create;
+#[cfg(any(feature = "alloc", doc))]
+mod edit;
 mod guid;
-
+mod hybrid;
+mod iter;
+mod repair;
+mod resize;
+mod update;
+
+pub mod ab;
+pub mod dps;
 pub mod error;
 pub mod header;
 pub mod mbr;
@@ -38,15 +56,57 @@ pub mod part;
 #[cfg(any(feature = "std", doc))]
 pub mod std;
 
-use crate::mbr::{MBRPartitionRecord, MasterBootRecord};
-use crate::part::{GPTPartHeader, GPTTypeGuid};
+use crate::mbr::{MBRPartitionRecord, MasterBootRecord, MbrType};
+use crate::part::{GPTPartHeader, GPTTypeGuid, RawPartHeader};
 
+#[doc(inline)]
+pub use block::BlockDeviceSize;
+#[doc(inline)]
+pub use create::GPTCreateOptions;
 #[doc(inline)]
 pub use guid::GUID;
+#[doc(inline)]
+pub use hybrid::HybridPartition;
+#[doc(inline)]
+pub use iter::{Partitions, PartitionsBuf};
 
 pub struct GPT<T> {
     block: T,
     header: GPTHeader,
+    /// Start of the second copy of the partition entry array, pointed to by the other header.
+    alt_p_entry_lba: u64,
+    /// Partition records of the MBR in front of the partition table.
+    mbr_partition: [MBRPartitionRecord; 4],
+    /// Index of the GPT partition mirrored by each slot of a hybrid MBR.
+    mbr_hybrid: [Option<u32>; 4],
+    mbr_type: MbrType,
+
+    /// Partition entry array with changes not yet written by [`GPT::commit`].
+    #[cfg(any(feature = "alloc", doc))]
+    staged: Option<Vec<u8>>,
+
+    /// Validated partition entry array of `header`, as it is on the device. Lookups are served
+    /// from it instead of reading the device, see [`GPT::reload`].
+    #[cfg(any(feature = "alloc", doc))]
+    cache: Option<Vec<u8>>,
+}
+
+impl<T> GPT<T> {
+    fn new(block: T, header: GPTHeader, alt_p_entry_lba: u64) -> Self {
+        Self {
+            block,
+            header,
+            alt_p_entry_lba,
+            mbr_partition: [MBRPartitionRecord::EMPTY; 4],
+            mbr_hybrid: [None; 4],
+            mbr_type: MbrType::Protective,
+
+            #[cfg(any(feature = "alloc", doc))]
+            staged: None,
+            #[cfg(any(feature = "alloc", doc))]
+            cache: None,
+        }
+    }
 }
 
 impl<T> GPT<T>
@@ -54,9 +114,50 @@ where
     T: BlockDevice,
     GPTError: From<T::Error>,
 {
-    // This checks that the other header is okay, so cannot unwrap it in if header
-    #[allow(clippy::unnecessary_unwrap)]
+    /// Open the GUID partition table on `block`.
+    ///
+    /// With the `alloc` feature, the validated partition entry array is kept in memory, and
+    /// lookups don't read the device again.
+    ///
+    /// The protective partition of the MBR may be in any slot. Other partitions in the MBR make
+    /// it a hybrid MBR, see [`Self::mbr_type`].
+    ///
+    /// If only one of the headers is valid, [`GPTParseError::BrokenHeader`] is returned with a
+    /// usable [`GPT`] based on the valid header. If the primary header cannot be used, the backup
+    /// header is searched at the end of the disk, as given by the protective MBR.
     pub fn open(block: T) -> Result<Self, GPTParseError<T>> {
+        Self::open_default(block, None)
+    }
+
+    /// Like [`Self::open`], but uses the size of the device to locate the backup header, and to
+    /// check that the headers and the protective MBR lie on the device.
+    pub fn open_sized(block: T) -> Result<Self, GPTParseError<T>>
+    where
+        T: BlockDeviceSize,
+    {
+        let num_blocks = block.num_blocks()?;
+        Self::open_default(block, Some(num_blocks))
+    }
+
+    /// Like [`Self::open`], but uses `buf` instead of an internal buffer of
+    /// [`DEFAULT_PARTTABLE_SIZE`] bytes. `buf` has to hold at least one block, partition entry
+    /// arrays bigger than `buf` are read in chunks.
+    ///
+    /// The partition entry array is not kept in memory, until [`Self::reload`] is called.
+    pub fn open_with_buf(block: T, buf: &mut [u8]) -> Result<Self, GPTParseError<T>> {
+        Self::open_inner(block, None, buf, false)
+    }
+
+    /// Like [`Self::open_sized`], but uses `buf` like [`Self::open_with_buf`].
+    pub fn open_sized_with_buf(block: T, buf: &mut [u8]) -> Result<Self, GPTParseError<T>>
+    where
+        T: BlockDeviceSize,
+    {
+        let num_blocks = block.num_blocks()?;
+        Self::open_inner(block, Some(num_blocks), buf, false)
+    }
+
+    fn open_default(block: T, num_blocks: Option<u64>) -> Result<Self, GPTParseError<T>> {
         #[cfg(not(feature = "alloc"))]
         let mut buf = [0u8; DEFAULT_PARTTABLE_SIZE as usize];
 
@@ -68,84 +169,246 @@ where
             buf
         };
 
-        // TODO: read address from MBR
-        block.read(&mut buf, 0, 1)?;
-        let mbr = unsafe { MasterBootRecord::from_buf(&buf) }?;
+        Self::open_inner(block, num_blocks, &mut buf, true)
+    }
 
-        mbr.verify(None)?;
-        if mbr.partition[0].os_indicator != MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE {
-            // This is not a protective MBR, but a possible a MBR with one or more GPT partitions.
-            // Bailing out
-            return Err(GPTError::NoGPT.into());
+    /// Open the partition table, using `buf` as scratch space. If `cache` is set, the partition
+    /// entry array of the used header is kept in memory.
+    fn open_inner(
+        block: T,
+        num_blocks: Option<u64>,
+        buf: &mut [u8],
+        cache: bool,
+    ) -> Result<Self, GPTParseError<T>> {
+        // Without an allocator, there is nothing to keep the partition entry array in.
+        #[cfg(not(feature = "alloc"))]
+        let _ = cache;
+
+        let block_size = T::BLOCK_SIZE as usize;
+        if buf.len() < core::cmp::max(block_size, 512) {
+            return Err(GPTError::UnexpectedEOF.into());
         }
 
-        let header_lba = mbr.partition[0].starting_lba() as usize;
+        // TODO: read address from MBR
+        block.read(&mut buf[..block_size], 0, 1)?;
+        let mbr = MasterBootRecord::parse(buf)?;
 
-        // mbr has to be clean up, before buf is used again, as mbr points into buf.
-        drop(mbr);
-        block.read(&mut buf, header_lba, 1)?;
+        mbr.verify(
+            num_blocks.map(|num_blocks| core::cmp::min(num_blocks, u32::MAX as u64) as u32),
+        )?;
+        // A hybrid MBR can have the protective partition in any slot.
+        let protective = match mbr.partition.iter().find(|part| {
+            part.os_indicator == MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE && !part.is_empty()
+        }) {
+            Some(protective) => *protective,
+            None => return Err(GPTError::NoGPT.into()),
+        };
 
-        let m_header = GPTHeader::parse(&buf)?;
+        let header_lba = protective.starting_lba() as u64;
+
+        // The protective partition covers the whole disk, unless the disk is to big for the MBR.
+        // In a hybrid MBR it may only cover the GPT structures, so this is just a guess.
+        let last_lba = num_blocks
+            .map(|num_blocks| num_blocks.saturating_sub(1))
+            .or_else(|| {
+                let size_in_lba = protective.size_in_lba();
+                if size_in_lba != u32::MAX {
+                    (header_lba + size_in_lba as u64).checked_sub(1)
+                } else {
+                    None
+                }
+            });
+
+        // Read the header at lba, and its partition entry array. Only returns valid headers.
+        let load_header = |lba: u64, buf: &mut [u8]| -> Result<GPTHeader> {
+            block.read(&mut buf[..block_size], lba as usize, 1)?;
+            let header = GPTHeader::parse(buf)?;
+
+            if header.my_lba != lba {
+                return Err(GPTError::InvalidLba(header.my_lba));
+            }
+            header.validate_crc()?;
+            if let Some(num_blocks) = num_blocks {
+                header.validate_lbas(num_blocks, T::BLOCK_SIZE)?;
+            }
 
-        let p_table_size = m_header.size_of_p_entry * m_header.num_parts;
-        #[cfg(not(feature = "alloc"))]
-        if p_table_size > DEFAULT_PARTTABLE_SIZE {
-            return Err(GPTError::NoAllocator.into());
-        }
+            let p_crc32 = stream_part_crc(&block, &header, buf, |_, _| ())?;
+            if p_crc32 != header.p_crc32 {
+                return Err(GPTError::InvalidCrcParts(p_crc32, header.p_crc32));
+            }
+
+            Ok(header)
+        };
+
+        let m_header = load_header(header_lba, buf);
 
+        // Keep the partition entry array of the primary header, before reading the backup.
         #[cfg(feature = "alloc")]
-        if p_table_size > buf.len() as u32 {
-            buf.try_reserve_exact(p_table_size as usize - buf.len())?; // Catch allocation errors
-            buf.resize(p_table_size as usize, 0);
-        }
+        let m_table = match &m_header {
+            Ok(m_header) if cache => Some(cached_table(&block, m_header, buf)?),
+            _ => None,
+        };
 
-        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+        // Without a valid primary header, the backup header is expected at the end of the disk.
+        let backup_lba = match &m_header {
+            Ok(m_header) => Some(m_header.other_lba),
+            Err(_) => last_lba,
+        };
+        let b_header = match backup_lba {
+            Some(backup_lba) => load_header(backup_lba, buf),
+            None => Err(GPTError::NoGPT),
+        };
+
+        let (header, alt_p_entry_lba, broken) = match (m_header, b_header) {
+            (Ok(m_header), Ok(b_header)) => (m_header, b_header.p_entry_lba, None),
+            (Ok(m_header), Err(e)) => {
+                let alt_p_entry_lba = m_header.alternate_p_entry_lba(T::BLOCK_SIZE)?;
+                (m_header, alt_p_entry_lba, Some((GptHeaderType::Backup, e)))
+            }
+            (Err(e), Ok(b_header)) => {
+                let alt_p_entry_lba = b_header.alternate_p_entry_lba(T::BLOCK_SIZE)?;
+                (b_header, alt_p_entry_lba, Some((GptHeaderType::Main, e)))
+            }
+            (Err(_), Err(_)) => return Err(GPTError::NoGPT.into()),
+        };
+
+        // The primary table is only missing, if the backup header is used. Its partition entry
+        // array was the last one read into buf.
+        #[cfg(feature = "alloc")]
+        let m_table = match m_table {
+            Some(table) => Some(table),
+            None if cache => Some(cached_table(&block, &header, buf)?),
+            None => None,
+        };
 
-        block.read(&mut buf, m_header.p_entry_lba as usize, blocks as usize)?;
-
-        let m_header_valid = m_header.validate(header_lba as u64, &buf);
-
-        block.read(&mut buf, m_header.other_lba as usize, 1)?;
-        let b_header = GPTHeader::parse(&buf)?;
-
-        block.read(&mut buf, b_header.p_entry_lba as usize, blocks as usize)?;
-
-        let b_header_valid = b_header.validate(m_header.other_lba as u64, &buf);
-
-        if m_header_valid.is_err() || b_header_valid.is_err() {
-            return if m_header_valid.is_ok() {
-                Err(GPTParseError::BrokenHeader(
-                    Self {
-                        block,
-                        header: m_header,
-                    },
-                    GptHeaderType::Backup,
-                    b_header_valid.unwrap_err(),
-                ))
-            } else if b_header_valid.is_ok() {
-                Err(GPTParseError::BrokenHeader(
-                    Self {
-                        block,
-                        header: b_header,
-                    },
-                    GptHeaderType::Main,
-                    m_header_valid.unwrap_err(),
-                ))
-            } else {
-                Err(GPTError::NoGPT.into())
-            };
+        let mut gpt = Self::new(block, header, alt_p_entry_lba);
+        #[cfg(feature = "alloc")]
+        {
+            gpt.cache = m_table;
         }
+        gpt.mbr_partition = mbr.partition;
+        gpt.detect_mbr_type(buf)?;
 
-        Ok(Self {
-            block,
-            header: m_header,
-        })
+        match broken {
+            None => Ok(gpt),
+            Some((header_type, e)) => Err(GPTParseError::BrokenHeader(gpt, header_type, e)),
+        }
     }
 
     pub fn get_block(self) -> T {
         self.block
     }
 
+    /// Read the header in use and its partition entry array again from the device, and keep the
+    /// partition entry array in memory for lookups. The location of the backup partition entry
+    /// array and the MBR are read again as well.
+    ///
+    /// Needed after the partition table was changed on the device other than through this
+    /// [`GPT`]. Fails with [`GPTError::StagedChanges`] if changes are staged, as they were made
+    /// against the old partition table. On errors, the previous state is kept.
+    #[cfg(any(feature = "alloc", doc))]
+    pub fn reload(&mut self) -> Result<()> {
+        if self.has_staged_changes() {
+            return Err(GPTError::StagedChanges);
+        }
+
+        let block_size = T::BLOCK_SIZE as usize;
+        let lba = self.header.my_lba;
+
+        let mut buf = Vec::new();
+        buf.try_reserve_exact(core::cmp::max(block_size, 512))?; // Catch allocation errors
+        buf.resize(core::cmp::max(block_size, 512), 0);
+        self.block.read(&mut buf[..block_size], lba as usize, 1)?;
+        let header = GPTHeader::parse(&buf)?;
+
+        let p_table_size = header.size_of_p_entry as usize * header.num_parts as usize;
+        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+        let mut table = read_buf(
+            header.p_entry_lba as usize,
+            p_table_size,
+            &self.block,
+            blocks,
+        )?;
+        header.validate(lba, &table)?;
+        table.truncate(p_table_size);
+
+        // Take the backup partition entry array from the other header, if it is valid.
+        self.block
+            .read(&mut buf[..block_size], header.other_lba as usize, 1)?;
+        let other = GPTHeader::parse(&buf).and_then(|other| {
+            if other.my_lba != header.other_lba {
+                return Err(GPTError::InvalidLba(other.my_lba));
+            }
+            other.validate_crc()?;
+            Ok(other)
+        });
+        let alt_p_entry_lba = match other {
+            Ok(other) => other.p_entry_lba,
+            Err(_) => header.alternate_p_entry_lba(T::BLOCK_SIZE)?,
+        };
+
+        self.block.read(&mut buf[..block_size], 0, 1)?;
+        let mbr = MasterBootRecord::parse(&buf)?;
+        mbr.verify(None)?;
+
+        let previous = (
+            core::mem::replace(&mut self.header, header),
+            core::mem::replace(&mut self.alt_p_entry_lba, alt_p_entry_lba),
+            self.cache.replace(table),
+            core::mem::replace(&mut self.mbr_partition, mbr.partition),
+            self.mbr_hybrid,
+            self.mbr_type,
+        );
+        if let Err(e) = self.detect_mbr_type(&mut buf) {
+            let (header, alt_p_entry_lba, cache, mbr_partition, mbr_hybrid, mbr_type) = previous;
+            self.header = header;
+            self.alt_p_entry_lba = alt_p_entry_lba;
+            self.cache = cache;
+            self.mbr_partition = mbr_partition;
+            self.mbr_hybrid = mbr_hybrid;
+            self.mbr_type = mbr_type;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Write `table` as the new partition entry array to both copies of the partition table.
+    ///
+    /// The backup copy is written first and the primary one last, so an interrupted write always
+    /// leaves one valid copy on disk. `buf` is used as scratch space of at least one block.
+    #[cfg(feature = "alloc")]
+    fn write_table(&mut self, table: &[u8], buf: &mut [u8]) -> Result<()> {
+        let mut header = self.header.clone();
+        header.update_part_crc(table)?;
+        let alt_header = header.alternate(self.alt_p_entry_lba);
+
+        let table = &table[..header.num_parts as usize * header.size_of_p_entry as usize];
+
+        let (primary, backup) = if header.my_lba < alt_header.my_lba {
+            (&header, &alt_header)
+        } else {
+            (&alt_header, &header)
+        };
+        for header in [backup, primary] {
+            write_table_blocks(&self.block, header.p_entry_lba, table, buf)?;
+            write_header(&self.block, header, buf)?;
+        }
+
+        if let Some(cache) = self.cache.as_mut() {
+            cache.clear();
+            cache.extend_from_slice(table);
+        }
+        self.header = header;
+
+        Ok(())
+    }
+
+    /// The header used to access the partition table.
+    pub fn header(&self) -> &GPTHeader {
+        &self.header
+    }
+
     pub fn get_partition_buf<PT, PA>(&self, idx: u32, buf: &[u8]) -> Result<GPTPartHeader<PT, PA>>
     where
         PT: GPTTypeGuid,
@@ -164,6 +427,11 @@ where
         GPTPartHeader::parse(&buf[offset as usize..])
     }
 
+    /// Get the partition entry at `idx`, out of the cache or by reading the blocks holding it.
+    ///
+    /// Without the `alloc` feature, the blocks are read into a buffer of
+    /// [`ENTRY_BUF_SIZE`] bytes on the stack, and it fails with [`GPTError::NoAllocator`] if
+    /// they don't fit. Use [`Self::get_partition_with_buf`] for bigger blocks.
     pub fn get_partition<PT, PA>(&self, idx: u32) -> Result<GPTPartHeader<PT, PA>>
     where
         PT: GPTTypeGuid,
@@ -176,18 +444,75 @@ where
             return Err(GPTError::InvalidData);
         }
 
-        let p_table_size = self.header.size_of_p_entry as usize * self.header.num_parts as usize;
+        #[cfg(feature = "alloc")]
+        if let Some(cache) = &self.cache {
+            return self.get_partition_buf(idx, cache);
+        }
 
-        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+        // Only read the blocks holding the entry.
+        let block_size = T::BLOCK_SIZE as u64;
+        let offset = idx as u64 * self.header.size_of_p_entry as u64;
+        let blocks = ceil64(offset % block_size + RawPartHeader::SIZE as u64, block_size);
+        let len = (blocks * block_size) as usize;
 
-        let buf = read_buf(
-            self.header.p_entry_lba as usize,
-            p_table_size,
-            &self.block,
-            blocks,
-        )?;
+        #[cfg(not(feature = "alloc"))]
+        let mut buf = [0u8; ENTRY_BUF_SIZE];
 
-        self.get_partition_buf(idx, &buf)
+        #[cfg(feature = "alloc")]
+        let mut buf = {
+            let mut buf = Vec::new();
+            buf.try_reserve_exact(len)?; // Catch allocation errors
+            buf.resize(len, 0);
+            buf
+        };
+
+        let buf = buf.get_mut(..len).ok_or(GPTError::NoAllocator)?;
+        self.get_partition_with_buf(idx, buf)
+    }
+
+    /// Like [`Self::get_partition_with_buf`], but reads the whole partition entry array in
+    /// chunks into `buf` to check its crc32, so the returned entry can be trusted. `buf` has to
+    /// hold at least one block.
+    ///
+    /// Fails with [`GPTError::InvalidCrcParts`] if the partition entry array on the device does
+    /// not match the header. A cached partition entry array was already verified, and is used
+    /// without reading the device.
+    pub fn get_partition_verified_with_buf<PT, PA>(
+        &self,
+        idx: u32,
+        buf: &mut [u8],
+    ) -> Result<GPTPartHeader<PT, PA>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        if idx >= self.header.num_parts {
+            return Err(GPTError::InvalidData);
+        }
+
+        #[cfg(feature = "alloc")]
+        if let Some(cache) = &self.cache {
+            let offset = idx as usize * self.header.size_of_p_entry as usize;
+            return GPTPartHeader::parse(&cache[offset..]);
+        }
+
+        // Pick the entry out of the chunks passing by.
+        let offset = idx as usize * self.header.size_of_p_entry as usize;
+        let mut entry = [0u8; RawPartHeader::SIZE];
+        let p_crc32 = stream_part_crc(&self.block, &self.header, buf, |start, chunk| {
+            let from = core::cmp::max(offset, start);
+            let to = core::cmp::min(offset + entry.len(), start + chunk.len());
+            if from < to {
+                entry[from - offset..to - offset].copy_from_slice(&chunk[from - start..to - start]);
+            }
+        })?;
+        if p_crc32 != self.header.p_crc32 {
+            return Err(GPTError::InvalidCrcParts(p_crc32, self.header.p_crc32));
+        }
+
+        GPTPartHeader::parse(&entry)
     }
 
     pub fn get_first_partition_of_type_buf<PT, PA>(
@@ -203,16 +528,14 @@ where
         GPTError: From<<PA as TryFrom<u64>>::Error>,
         PT: Eq,
     {
-        let mut idx = 0;
-
-        loop {
+        for idx in 0..self.header.num_parts {
             let part = self.get_partition_buf(idx, buf)?;
             if part.type_guid == guid {
                 return Ok(part);
             }
-
-            idx += 1;
         }
+
+        Err(GPTError::InvalidData)
     }
 
     pub fn get_first_partition_of_type<PT, PA>(&self, guid: PT) -> Result<GPTPartHeader<PT, PA>>
@@ -224,6 +547,11 @@ where
         GPTError: From<<PA as TryFrom<u64>>::Error>,
         PT: Eq,
     {
+        #[cfg(feature = "alloc")]
+        if let Some(cache) = &self.cache {
+            return self.get_first_partition_of_type_buf(guid, cache);
+        }
+
         let p_table_size = self.header.size_of_p_entry as usize * self.header.num_parts as usize;
 
         let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
@@ -237,6 +565,104 @@ where
 
         self.get_first_partition_of_type_buf(guid, &buf)
     }
+
+    /// Like [`Self::get_first_partition_of_type`], but reads the partition entry array in chunks
+    /// into `buf`. `buf` has to hold at least one block.
+    pub fn get_first_partition_of_type_with_buf<PT, PA>(
+        &self,
+        guid: PT,
+        buf: &mut [u8],
+    ) -> Result<GPTPartHeader<PT, PA>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+        PT: Eq,
+    {
+        let parts = self.partitions_with_buf::<PT, PA>(buf);
+        match find_partition(parts, |part| part.type_guid == guid)? {
+            Some((_, part)) => Ok(part),
+            None => Err(GPTError::InvalidData),
+        }
+    }
+
+    /// Find the partition with the unique partition guid `guid` (`PARTUUID`), and return it
+    /// together with its index.
+    pub fn find_by_guid<PT, PA>(&self, guid: &GUID) -> Result<Option<(u32, GPTPartHeader<PT, PA>)>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        let parts = self.used_partitions::<PT, PA>()?;
+        find_partition(parts, |part| part.guid == *guid)
+    }
+
+    /// Like [`Self::find_by_guid`], but reads the partition entry array in chunks into `buf`.
+    /// `buf` has to hold at least one block.
+    pub fn find_by_guid_with_buf<PT, PA>(
+        &self,
+        guid: &GUID,
+        buf: &mut [u8],
+    ) -> Result<Option<(u32, GPTPartHeader<PT, PA>)>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        let parts = self.used_partitions_with_buf::<PT, PA>(buf);
+        find_partition(parts, |part| part.guid == *guid)
+    }
+
+    /// Find the first partition named `name` (`PARTLABEL`), and return it together with its
+    /// index. The name is compared without allocations, see [`GPTPartHeader::name_eq`].
+    pub fn find_by_name<PT, PA>(&self, name: &str) -> Result<Option<(u32, GPTPartHeader<PT, PA>)>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        let parts = self.used_partitions::<PT, PA>()?;
+        find_partition(parts, |part| part.name_eq(name))
+    }
+
+    /// Like [`Self::find_by_name`], but reads the partition entry array in chunks into `buf`.
+    /// `buf` has to hold at least one block.
+    pub fn find_by_name_with_buf<PT, PA>(
+        &self,
+        name: &str,
+        buf: &mut [u8],
+    ) -> Result<Option<(u32, GPTPartHeader<PT, PA>)>>
+    where
+        PT: GPTTypeGuid,
+        GPTError: From<<PT as TryFrom<[u8; 16]>>::Error>,
+        PA: TryFrom<u64>,
+        GPTError: From<<PA as TryFrom<u64>>::Error>,
+    {
+        let parts = self.used_partitions_with_buf::<PT, PA>(buf);
+        find_partition(parts, |part| part.name_eq(name))
+    }
+}
+
+/// Return the first partition of `parts` matching `f`.
+fn find_partition<I, F, PT, PA>(parts: I, f: F) -> Result<Option<(u32, GPTPartHeader<PT, PA>)>>
+where
+    I: Iterator<Item = Result<(u32, GPTPartHeader<PT, PA>)>>,
+    F: Fn(&GPTPartHeader<PT, PA>) -> bool,
+    PT: GPTTypeGuid,
+{
+    for part in parts {
+        let (idx, part) = part?;
+        if f(&part) {
+            return Ok(Some((idx, part)));
+        }
+    }
+
+    Ok(None)
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -250,11 +676,12 @@ where
     GPTError: From<T::Error>,
 {
     let mut buf = [0u8; DEFAULT_PARTTABLE_SIZE as usize];
-    if size > DEFAULT_PARTTABLE_SIZE as usize {
+    let len = blocks * T::BLOCK_SIZE as usize;
+    if size > buf.len() || len > buf.len() {
         return Err(GPTError::NoAllocator);
     }
 
-    block.read(&mut buf, start_lba, blocks)?;
+    block.read(&mut buf[..len], start_lba, blocks)?;
 
     Ok(buf)
 }
@@ -276,22 +703,149 @@ where
         buf
     };
 
-    if size > buf.len() {
-        buf.try_reserve_exact(size - buf.len())?; // Catch allocation errors
-        buf.resize(size, 0);
+    // The last block is read completely, even if the table ends within it.
+    let len = core::cmp::max(size, blocks * T::BLOCK_SIZE as usize);
+    if len > buf.len() {
+        buf.try_reserve_exact(len - buf.len())?; // Catch allocation errors
+        buf.resize(len, 0);
     }
 
-    block.read(&mut buf, start_lba, blocks)?;
+    block.read(
+        &mut buf[..blocks * T::BLOCK_SIZE as usize],
+        start_lba,
+        blocks,
+    )?;
 
     Ok(buf)
 }
 
-/*fn ceil32(mut a: u32, b: u32) -> u32 {
-    a += b - (a % b);
-    a / b
+/// Copy the partition entry array of `header` out of `buf`, where it was left by
+/// [`stream_part_crc`]. If it did not fit into `buf` at once, it is read and validated again.
+#[cfg(feature = "alloc")]
+fn cached_table<T: BlockDevice>(block: &T, header: &GPTHeader, buf: &[u8]) -> Result<Vec<u8>>
+where
+    GPTError: From<T::Error>,
+{
+    let p_table_size = header.size_of_p_entry as usize * header.num_parts as usize;
+    let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64) as usize;
+
+    if blocks * T::BLOCK_SIZE as usize <= buf.len() {
+        let mut table = Vec::new();
+        table.try_reserve_exact(p_table_size)?; // Catch allocation errors
+        table.extend_from_slice(&buf[..p_table_size]);
+        return Ok(table);
+    }
+
+    let mut table = read_buf(header.p_entry_lba as usize, p_table_size, block, blocks)?;
+    header.validate_part_crc(&table)?;
+    table.truncate(p_table_size);
+
+    Ok(table)
+}
+
+/// Calculate the crc32 of the partition entry array of `header`, reading it in chunks of as many
+/// blocks as fit into `buf`. Every chunk is passed to `f` together with its offset in the array.
+fn stream_part_crc<T, F>(block: &T, header: &GPTHeader, buf: &mut [u8], mut f: F) -> Result<u32>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+    F: FnMut(usize, &[u8]),
+{
+    let block_size = T::BLOCK_SIZE as u64;
+    let chunk = buf.len() as u64 / block_size;
+    if chunk == 0 {
+        return Err(GPTError::UnexpectedEOF);
+    }
+
+    let p_table_size = header.size_of_p_entry as u64 * header.num_parts as u64;
+    let blocks = ceil64(p_table_size, block_size);
+
+    let mut digest = crc32::Digest::new(crc32::IEEE);
+    let mut lba = 0;
+    while lba < blocks {
+        let count = core::cmp::min(chunk, blocks - lba);
+        let buf = &mut buf[..(count * block_size) as usize];
+        block.read(buf, (header.p_entry_lba + lba) as usize, count as usize)?;
+
+        let len = core::cmp::min(count * block_size, p_table_size - lba * block_size);
+        digest.write(&buf[..len as usize]);
+        f((lba * block_size) as usize, &buf[..len as usize]);
+        lba += count;
+    }
+
+    Ok(digest.sum32())
+}
+
+/// Write `header` into its own block, using `buf` as scratch space of at least one block.
+fn write_header<T: BlockDevice>(block: &T, header: &GPTHeader, buf: &mut [u8]) -> Result<()>
+where
+    GPTError: From<T::Error>,
+{
+    let buf = buf
+        .get_mut(..T::BLOCK_SIZE as usize)
+        .ok_or(GPTError::UnexpectedEOF)?;
+    buf.fill(0);
+    header.write_to(buf)?;
+    block.write(buf, header.my_lba as usize, 1)?;
+
+    Ok(())
+}
+
+/// Write the partition entry array `table` to `lba`. A trailing partial block is padded with
+/// zeros, using `buf` as scratch space of at least one block.
+fn write_table_blocks<T: BlockDevice>(
+    block: &T,
+    lba: u64,
+    table: &[u8],
+    buf: &mut [u8],
+) -> Result<()>
+where
+    GPTError: From<T::Error>,
+{
+    let block_size = T::BLOCK_SIZE as usize;
+    let blocks = table.len() / block_size;
+
+    if blocks > 0 {
+        block.write(&table[..blocks * block_size], lba as usize, blocks)?;
+    }
+
+    let rest = &table[blocks * block_size..];
+    if !rest.is_empty() {
+        let buf = buf.get_mut(..block_size).ok_or(GPTError::UnexpectedEOF)?;
+        buf.fill(0);
+        buf[..rest.len()].copy_from_slice(rest);
+        block.write(buf, lba as usize + blocks, 1)?;
+    }
+
+    Ok(())
+}
+
+/*fn ceil32(a: u32, b: u32) -> u32 {
+    match a % b {
+        0 => a / b,
+        _ => a / b + 1,
+    }
 }*/
 
-fn ceil64(mut a: u64, b: u64) -> u64 {
-    a += b - (a % b);
-    a / b
+/// Divide `a` by `b`, rounding up. Used to count the blocks needed for `a` bytes, so exact
+/// multiples of the block size don't get an extra block.
+fn ceil64(a: u64, b: u64) -> u64 {
+    match a % b {
+        0 => a / b,
+        _ => a / b + 1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ceil64;
+
+    #[test]
+    fn ceil() {
+        assert_eq!(ceil64(0, 512), 0);
+        assert_eq!(ceil64(1, 512), 1);
+        assert_eq!(ceil64(512, 512), 1);
+        assert_eq!(ceil64(16384, 512), 32);
+        assert_eq!(ceil64(16385, 512), 33);
+    }
 }
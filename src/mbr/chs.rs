@@ -0,0 +1,170 @@
+use crate::{GPTError, Result};
+
+/// Disk geometry used to convert between LBAs and CHS addresses.
+///
+/// Modern disks don't have a real geometry anymore, the BIOS and partitioning tools agree on
+/// [`Geometry::DEFAULT`] with 255 heads and 63 sectors per track.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Geometry {
+    heads: u32,
+    sectors: u32,
+}
+
+impl Geometry {
+    /// 255 heads and 63 sectors per track.
+    pub const DEFAULT: Self = Self {
+        heads: 255,
+        sectors: 63,
+    };
+
+    /// Create a geometry with 1 to 255 `heads` and 1 to 63 `sectors` per track.
+    pub fn new(heads: u32, sectors: u32) -> Result<Self> {
+        if !(1..=255).contains(&heads) || !(1..=63).contains(&sectors) {
+            return Err(GPTError::InvalidData);
+        }
+
+        Ok(Self { heads, sectors })
+    }
+
+    /// Number of heads.
+    pub fn heads(&self) -> u32 {
+        self.heads
+    }
+
+    /// Number of sectors per track.
+    pub fn sectors(&self) -> u32 {
+        self.sectors
+    }
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Cylinder, head and sector address of a block, as stored in an MBR partition record.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Chs {
+    /// Cylinder, 0 to 1023.
+    pub cylinder: u16,
+    /// Head, 0 to 254.
+    pub head: u8,
+    /// Sector in the track, 1 to 63. Sector 0 is invalid.
+    pub sector: u8,
+}
+
+impl Chs {
+    /// Highest address, used for all blocks beyond it.
+    pub const MAX: Self = Self {
+        cylinder: 1023,
+        head: 254,
+        sector: 63,
+    };
+
+    /// Convert `lba` to a CHS address with `geometry`. Blocks after the last cylinder 1023
+    /// saturate to [`Self::MAX`].
+    pub fn from_lba(lba: u64, geometry: Geometry) -> Self {
+        let heads = geometry.heads as u64;
+        let sectors = geometry.sectors as u64;
+
+        let cylinder = lba / (heads * sectors);
+        if cylinder > Self::MAX.cylinder as u64 {
+            return Self::MAX;
+        }
+
+        Self {
+            cylinder: cylinder as u16,
+            head: ((lba / sectors) % heads) as u8,
+            sector: (lba % sectors + 1) as u8,
+        }
+    }
+
+    /// Convert the address back to an LBA with `geometry`. Returns `None` for invalid
+    /// addresses, which don't fit into `geometry` or have sector 0.
+    pub fn to_lba(&self, geometry: Geometry) -> Option<u64> {
+        if self.sector == 0
+            || self.sector as u32 > geometry.sectors
+            || self.head as u32 >= geometry.heads
+        {
+            return None;
+        }
+
+        let heads = geometry.heads as u64;
+        let sectors = geometry.sectors as u64;
+        Some((self.cylinder as u64 * heads + self.head as u64) * sectors + self.sector as u64 - 1)
+    }
+
+    /// Check if the address is the one of `lba` with `geometry`. Addresses computed for blocks
+    /// beyond the last cylinder are saturated, and don't represent their block.
+    pub fn represents(&self, lba: u64, geometry: Geometry) -> bool {
+        self.to_lba(geometry) == Some(lba)
+    }
+
+    /// Decode the address from the head, sector and track bytes of an MBR partition record. The
+    /// upper two bits of the cylinder are stored in the upper bits of the sector byte.
+    pub fn from_bytes(bytes: [u8; 3]) -> Self {
+        let [head, sector, track] = bytes;
+        Self {
+            cylinder: ((sector as u16 & 0xc0) << 2) | track as u16,
+            head,
+            sector: sector & 0x3f,
+        }
+    }
+
+    /// Encode the address into the head, sector and track bytes of an MBR partition record.
+    pub fn to_bytes(&self) -> [u8; 3] {
+        [
+            self.head,
+            (self.sector & 0x3f) | ((self.cylinder >> 2) as u8 & 0xc0),
+            self.cylinder as u8,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chs, Geometry};
+
+    #[test]
+    fn lba_roundtrip() {
+        let geometry = Geometry::DEFAULT;
+        for lba in [0, 1, 62, 63, 16064, 16065, 1_000_000, 16_450_559] {
+            let chs = Chs::from_lba(lba, geometry);
+            assert_eq!(chs.to_lba(geometry), Some(lba));
+            assert_eq!(Chs::from_bytes(chs.to_bytes()), chs);
+        }
+
+        let chs = Chs::from_lba(2048, geometry);
+        assert_eq!((chs.cylinder, chs.head, chs.sector), (0, 32, 33));
+        assert_eq!(chs.to_bytes(), [32, 33, 0]);
+
+        // The last representable block encodes to the highest address.
+        let chs = Chs::from_lba(16_450_559, geometry);
+        assert_eq!(chs, Chs::MAX);
+        assert_eq!(chs.to_bytes(), [254, 255, 255]);
+        assert!(chs.represents(16_450_559, geometry));
+    }
+
+    #[test]
+    fn saturation() {
+        assert_eq!(Chs::from_lba(16_450_560, Geometry::DEFAULT), Chs::MAX);
+        assert!(!Chs::MAX.represents(16_450_560, Geometry::DEFAULT));
+        assert_eq!(Chs::from_lba(u64::MAX, Geometry::DEFAULT), Chs::MAX);
+
+        let small = Geometry::new(16, 32).unwrap();
+        assert_eq!(Chs::from_lba(1024 * 16 * 32, small), Chs::MAX);
+        let chs = Chs::from_lba(1024 * 16 * 32 - 1, small);
+        assert_eq!((chs.cylinder, chs.head, chs.sector), (1023, 15, 32));
+        assert_eq!(Chs::MAX.to_lba(small), None);
+    }
+
+    #[test]
+    fn geometry() {
+        assert!(Geometry::new(0, 63).is_err());
+        assert!(Geometry::new(256, 63).is_err());
+        assert!(Geometry::new(255, 64).is_err());
+        assert_eq!(Geometry::new(255, 63).unwrap(), Geometry::default());
+        assert_eq!(Chs::from_bytes([0, 0, 0]).to_lba(Geometry::DEFAULT), None);
+    }
+}
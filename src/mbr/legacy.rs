@@ -0,0 +1,203 @@
+use block_device::BlockDevice;
+
+use crate::mbr::{MBRPartitionRecord, MasterBootRecord};
+use crate::{GPTError, Result};
+
+/// Partition of a legacy MBR partition table, returned by [`MasterBootRecord::partitions`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MBRPartition {
+    /// Number of the partition, like Linux counts them: 1 to 4 for the primary partitions, 5
+    /// and up for the logical partitions in the extended partition.
+    pub number: u32,
+    /// Boot indicator of the record, `0x80` marks the partition as bootable.
+    pub boot_indicator: u8,
+    /// Type of the partition.
+    pub os_indicator: u8,
+    /// First logical block of the partition, counted from the start of the disk.
+    pub starting_lba: u64,
+    /// Size of the partition in logical blocks.
+    pub size_in_lba: u64,
+}
+
+impl MBRPartition {
+    /// Check if this is a logical partition inside an extended partition.
+    pub fn is_logical(&self) -> bool {
+        self.number > 4
+    }
+
+    /// Check if this is an extended partition, see [`MBRPartitionRecord::is_extended`].
+    pub fn is_extended(&self) -> bool {
+        MBRPartitionRecord::is_extended_type(self.os_indicator)
+    }
+
+    /// First logical block after the partition.
+    pub fn ending_lba(&self) -> u64 {
+        self.starting_lba + self.size_in_lba
+    }
+
+    fn new(number: u32, record: &MBRPartitionRecord, starting_lba: u64) -> Self {
+        Self {
+            number,
+            boot_indicator: record.boot_indicator,
+            os_indicator: record.os_indicator,
+            starting_lba,
+            size_in_lba: record.size_in_lba() as u64,
+        }
+    }
+}
+
+/// Extended partition whose chain of extended boot records is walked.
+struct Extended {
+    starting_lba: u64,
+    ending_lba: u64,
+    /// Next extended boot record, if any.
+    ebr_lba: Option<u64>,
+}
+
+/// Iterator over the partitions of a legacy MBR, returned by [`MasterBootRecord::partitions`].
+///
+/// Yields the used primary partitions, and after an extended partition the logical partitions
+/// of its chain of extended boot records (EBR). Stops after the first error.
+///
+/// Every logical partition has to lie between its own EBR and the next one. This makes sure the
+/// logical partitions don't overlap, and that the chain only moves forward, so it cannot loop.
+pub struct MBRPartitions<'a, T> {
+    block: &'a T,
+    buf: &'a mut [u8],
+    partition: [MBRPartitionRecord; 4],
+    idx: usize,
+    extended: Option<Extended>,
+    number: u32,
+}
+
+impl<'a, T> MBRPartitions<'a, T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Read the next EBR of `extended`, and return its logical partition.
+    fn next_logical(&mut self) -> Result<Option<MBRPartition>> {
+        loop {
+            let extended = match &mut self.extended {
+                Some(extended) => extended,
+                None => return Ok(None),
+            };
+            let ebr_lba = match extended.ebr_lba {
+                Some(ebr_lba) => ebr_lba,
+                None => {
+                    self.extended = None;
+                    return Ok(None);
+                }
+            };
+
+            let block_size = T::BLOCK_SIZE as usize;
+            let buf = self
+                .buf
+                .get_mut(..core::cmp::max(block_size, MasterBootRecord::SIZE))
+                .ok_or(GPTError::UnexpectedEOF)?;
+            self.block
+                .read(&mut buf[..block_size], ebr_lba as usize, 1)?;
+            let ebr = MasterBootRecord::parse(buf)?;
+            if ebr.signature() != MasterBootRecord::SIGNATURE {
+                return Err(GPTError::InvalidMbr);
+            }
+
+            let (logical, link) = (&ebr.partition[0], &ebr.partition[1]);
+
+            // The next EBR is relative to the start of the extended partition.
+            let next_lba = if link.is_empty() {
+                None
+            } else {
+                let next_lba = extended.starting_lba + link.starting_lba() as u64;
+                if !link.is_extended() || next_lba <= ebr_lba {
+                    return Err(GPTError::InvalidMbr);
+                }
+                Some(next_lba)
+            };
+            let limit = next_lba.unwrap_or(extended.ending_lba);
+            if limit > extended.ending_lba {
+                return Err(GPTError::InvalidMbr);
+            }
+            extended.ebr_lba = next_lba;
+
+            // An EBR without logical partition only links to the next one.
+            if logical.is_empty() {
+                continue;
+            }
+
+            // The logical partition is relative to its EBR.
+            let part = MBRPartition::new(
+                self.number,
+                logical,
+                ebr_lba + logical.starting_lba() as u64,
+            );
+            if part.starting_lba <= ebr_lba || part.ending_lba() > limit {
+                return Err(GPTError::OverlappingPartitions);
+            }
+            self.number += 1;
+
+            return Ok(Some(part));
+        }
+    }
+}
+
+impl<'a, T> Iterator for MBRPartitions<'a, T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    type Item = Result<MBRPartition>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_logical() {
+            Ok(Some(part)) => return Some(Ok(part)),
+            Ok(None) => (),
+            Err(e) => {
+                self.idx = self.partition.len();
+                self.extended = None;
+                return Some(Err(e));
+            }
+        }
+
+        while self.idx < self.partition.len() {
+            let idx = self.idx;
+            self.idx += 1;
+
+            let record = &self.partition[idx];
+            if record.is_empty() {
+                continue;
+            }
+
+            let part = MBRPartition::new(idx as u32 + 1, record, record.starting_lba() as u64);
+            if record.is_extended() {
+                self.extended = Some(Extended {
+                    starting_lba: part.starting_lba,
+                    ending_lba: part.ending_lba(),
+                    ebr_lba: Some(part.starting_lba),
+                });
+            }
+
+            return Some(Ok(part));
+        }
+
+        None
+    }
+}
+
+impl MasterBootRecord {
+    /// Iterate over the partitions of a legacy MBR partition table on `block`, including the
+    /// logical partitions in extended partitions.
+    ///
+    /// The extended boot records are read into `buf`, which has to hold at least one block. Use
+    /// [`Self::verify_partitions`] to check that the primary partitions don't overlap.
+    pub fn partitions<'a, T>(&self, block: &'a T, buf: &'a mut [u8]) -> MBRPartitions<'a, T> {
+        MBRPartitions {
+            block,
+            buf,
+            partition: self.partition,
+            idx: 0,
+            extended: None,
+            number: 5,
+        }
+    }
+}
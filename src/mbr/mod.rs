@@ -0,0 +1,487 @@
+use crate::{GPTError, Result};
+
+mod chs;
+mod legacy;
+pub use chs::{Chs, Geometry};
+pub use legacy::{MBRPartition, MBRPartitions};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C, packed)]
+pub struct MBRPartitionRecord {
+    pub boot_indicator: u8,
+    pub start_head: u8,
+    pub start_sector: u8,
+    pub start_track: u8,
+    pub os_indicator: u8,
+    pub end_head: u8,
+    pub end_sector: u8,
+    pub end_track: u8,
+    pub starting_lba: [u8; 4],
+    pub size_in_lba: [u8; 4],
+}
+
+impl MBRPartitionRecord {
+    /// Check if the partition is to be considered empty.
+    ///
+    /// A partition is considered empty if the [`Self::os_indicator`] or the [`Self::size_in_lba`]
+    /// is 0.
+    pub fn is_empty(&self) -> bool {
+        self.os_indicator == 0 || self.size_in_lba() == 0
+    }
+
+    /// Check if the partition is an extended partition, holding a chain of extended boot records
+    /// with logical partitions.
+    pub fn is_extended(&self) -> bool {
+        Self::is_extended_type(self.os_indicator)
+    }
+
+    /// Check if `os_indicator` is the type of an extended partition.
+    pub(crate) fn is_extended_type(os_indicator: u8) -> bool {
+        matches!(
+            os_indicator,
+            Self::EXTENDED_CHS_OS_TYPE | Self::EXTENDED_LBA_OS_TYPE | Self::LINUX_EXTENDED_OS_TYPE
+        )
+    }
+
+    /// Return the starting logical block as u32.
+    pub fn starting_lba(&self) -> u32 {
+        u32::from_le_bytes(self.starting_lba)
+    }
+
+    /// Return the size in logical blocks as u32.
+    pub fn size_in_lba(&self) -> u32 {
+        u32::from_le_bytes(self.size_in_lba)
+    }
+
+    /// Size of a partition record in the MBR.
+    pub const SIZE: usize = 16;
+
+    /// Create a partition record of type `os_indicator`, with the CHS addresses computed from
+    /// the LBAs with [`Geometry::DEFAULT`].
+    pub fn new(os_indicator: u8, starting_lba: u32, size_in_lba: u32) -> Self {
+        Self::new_with_geometry(os_indicator, starting_lba, size_in_lba, Geometry::DEFAULT)
+    }
+
+    /// Like [`Self::new`], but computes the CHS addresses with `geometry`.
+    pub fn new_with_geometry(
+        os_indicator: u8,
+        starting_lba: u32,
+        size_in_lba: u32,
+        geometry: Geometry,
+    ) -> Self {
+        let mut part = Self {
+            os_indicator,
+            ..Self::EMPTY
+        };
+        part.set_lbas_with_geometry(starting_lba, size_in_lba, geometry);
+        part
+    }
+
+    /// Set the LBAs of the partition, and update the CHS addresses to match them with
+    /// [`Geometry::DEFAULT`].
+    pub fn set_lbas(&mut self, starting_lba: u32, size_in_lba: u32) {
+        self.set_lbas_with_geometry(starting_lba, size_in_lba, Geometry::DEFAULT);
+    }
+
+    /// Like [`Self::set_lbas`], but computes the CHS addresses with `geometry`.
+    pub fn set_lbas_with_geometry(
+        &mut self,
+        starting_lba: u32,
+        size_in_lba: u32,
+        geometry: Geometry,
+    ) {
+        self.starting_lba = starting_lba.to_le_bytes();
+        self.size_in_lba = size_in_lba.to_le_bytes();
+
+        let last_lba = (starting_lba as u64 + size_in_lba as u64).saturating_sub(1);
+        self.set_start_chs(Chs::from_lba(starting_lba as u64, geometry));
+        self.set_end_chs(Chs::from_lba(last_lba, geometry));
+    }
+
+    /// CHS address of the first block of the partition.
+    pub fn start_chs(&self) -> Chs {
+        Chs::from_bytes([self.start_head, self.start_sector, self.start_track])
+    }
+
+    /// CHS address of the last block of the partition.
+    pub fn end_chs(&self) -> Chs {
+        Chs::from_bytes([self.end_head, self.end_sector, self.end_track])
+    }
+
+    /// Set the CHS address of the first block, without changing the LBAs.
+    pub fn set_start_chs(&mut self, chs: Chs) {
+        let [head, sector, track] = chs.to_bytes();
+        self.start_head = head;
+        self.start_sector = sector;
+        self.start_track = track;
+    }
+
+    /// Set the CHS address of the last block, without changing the LBAs.
+    pub fn set_end_chs(&mut self, chs: Chs) {
+        let [head, sector, track] = chs.to_bytes();
+        self.end_head = head;
+        self.end_sector = sector;
+        self.end_track = track;
+    }
+
+    /// Decode a partition record from the first 16 bytes of `buf`.
+    pub fn parse(buf: &[u8]) -> Result<Self> {
+        if buf.len() < Self::SIZE {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        Ok(Self {
+            boot_indicator: buf[0],
+            start_head: buf[1],
+            start_sector: buf[2],
+            start_track: buf[3],
+            os_indicator: buf[4],
+            end_head: buf[5],
+            end_sector: buf[6],
+            end_track: buf[7],
+            starting_lba: read_le_bytes!(buf, 8..12),
+            size_in_lba: read_le_bytes!(buf, 12..16),
+        })
+    }
+
+    /// Encode the partition record into the first 16 bytes of `buf`.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() < Self::SIZE {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        buf[..8].copy_from_slice(&[
+            self.boot_indicator,
+            self.start_head,
+            self.start_sector,
+            self.start_track,
+            self.os_indicator,
+            self.end_head,
+            self.end_sector,
+            self.end_track,
+        ]);
+        buf[8..12].copy_from_slice(&self.starting_lba);
+        buf[12..16].copy_from_slice(&self.size_in_lba);
+
+        Ok(())
+    }
+
+    /// Helper to calculate [`Self::starting_lba`] + [`Self::size_in_lba`] to get the ending lba.
+    /// Saturates at `u32::MAX`, as protective partitions of big disks reach past it.
+    pub fn ending_lba(&self) -> u32 {
+        self.starting_lba().saturating_add(self.size_in_lba())
+    }
+
+    /// Create the single partition record of a protective MBR, covering a disk with `num_blocks`
+    /// logical blocks. The size is capped at `0xFFFFFFFF` for disks which are too big.
+    ///
+    /// The CHS addresses are computed with [`Geometry::DEFAULT`]. Like the UEFI specification
+    /// asks for, the ending address is set to `0xFFFFFF` if the last block can't be represented.
+    pub fn new_protective(num_blocks: u64) -> Self {
+        let size_in_lba = core::cmp::min(num_blocks.saturating_sub(1), u32::MAX as u64) as u32;
+
+        let mut part = Self {
+            os_indicator: Self::GPT_PROTECTIVE_OS_TYPE,
+            ..Self::EMPTY
+        };
+        part.set_protective_lbas(1, size_in_lba);
+        part
+    }
+
+    /// Like [`Self::set_lbas`], but sets the ending CHS address to `0xFFFFFF` if the last block
+    /// can't be represented, as the UEFI specification asks for protective partitions.
+    pub fn set_protective_lbas(&mut self, starting_lba: u32, size_in_lba: u32) {
+        self.set_lbas(starting_lba, size_in_lba);
+
+        let last_lba = (starting_lba as u64 + size_in_lba as u64).saturating_sub(1);
+        if !self.end_chs().represents(last_lba, Geometry::DEFAULT) {
+            self.end_head = 0xff;
+            self.end_sector = 0xff;
+            self.end_track = 0xff;
+        }
+    }
+
+    /// An unused partition record.
+    pub const EMPTY: Self = Self {
+        boot_indicator: 0,
+        start_head: 0,
+        start_sector: 0,
+        start_track: 0,
+        os_indicator: 0,
+        end_head: 0,
+        end_sector: 0,
+        end_track: 0,
+        starting_lba: [0; 4],
+        size_in_lba: [0; 4],
+    };
+
+    /// Defines a UEFI system partition.
+    pub const UEFI_SYSTEM_OS_TYPE: u8 = 0xef;
+    /// Is used by a protective MBR to define a fake partition covering the entire disk.
+    pub const GPT_PROTECTIVE_OS_TYPE: u8 = 0xee;
+    /// Extended partition, addressed with CHS values.
+    pub const EXTENDED_CHS_OS_TYPE: u8 = 0x05;
+    /// Extended partition, addressed with LBAs.
+    pub const EXTENDED_LBA_OS_TYPE: u8 = 0x0f;
+    /// Linux extended partition.
+    pub const LINUX_EXTENDED_OS_TYPE: u8 = 0x85;
+}
+
+/// Kind of MBR in front of a GUID partition table, see [`crate::GPT::mbr_type`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MbrType {
+    /// Only the protective partition is used.
+    Protective,
+    /// Besides the protective partition, the MBR has partitions mirroring GPT partitions. A
+    /// partition mirrors the GPT partition covering the same blocks, whatever its type.
+    Hybrid,
+    /// A hybrid MBR with partitions that don't match any GPT partition.
+    HybridMismatch,
+}
+
+#[derive(Clone, Debug)]
+#[repr(C, packed)]
+pub struct MasterBootRecord {
+    /// x86 code used on a non-UEFI system to select an MBR partition record and load the first
+    /// logical block of that partition . This code shall not be executed on UEFI systems.
+    pub bootstrapcode: [u8; 440],
+    /// Unique Disk Signature This may be used by the OS to identify the disk from other disks in
+    /// the system. This value is always written by the OS and is never written by EFI firmware.
+    pub unique_mbr_signature: [u8; 4],
+    /// Unknown. This field shall not be used by UEFI firmware.
+    pub unknown: [u8; 2],
+    /// Array of four legacy MBR partition records [`MBRPartitionRecord`].
+    pub partition: [MBRPartitionRecord; 4],
+    pub signature: [u8; 2],
+}
+
+impl MasterBootRecord {
+    /// Size of the MBR on disk.
+    pub const SIZE: usize = 512;
+
+    /// Offset of the partition records.
+    const PARTITION_OFFSET: usize = 446;
+
+    /// Decode the MBR from the first 512 bytes of `buf`. `buf` needs no particular alignment.
+    pub fn parse(buf: &[u8]) -> Result<Self> {
+        if buf.len() < Self::SIZE {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        let mut partition = [MBRPartitionRecord::EMPTY; 4];
+        for (i, part) in partition.iter_mut().enumerate() {
+            let offset = Self::PARTITION_OFFSET + i * MBRPartitionRecord::SIZE;
+            *part = MBRPartitionRecord::parse(&buf[offset..])?;
+        }
+
+        Ok(Self {
+            bootstrapcode: read_le_bytes!(buf, 0..440),
+            unique_mbr_signature: read_le_bytes!(buf, 440..444),
+            unknown: read_le_bytes!(buf, 444..446),
+            partition,
+            signature: read_le_bytes!(buf, 510..512),
+        })
+    }
+
+    /// Encode the MBR into the first 512 bytes of `buf`.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() < Self::SIZE {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        buf[0..440].copy_from_slice(&self.bootstrapcode);
+        buf[440..444].copy_from_slice(&self.unique_mbr_signature);
+        buf[444..446].copy_from_slice(&self.unknown);
+        for (i, part) in self.partition.iter().enumerate() {
+            let offset = Self::PARTITION_OFFSET + i * MBRPartitionRecord::SIZE;
+            part.write_to(&mut buf[offset..])?;
+        }
+        buf[510..512].copy_from_slice(&self.signature);
+
+        Ok(())
+    }
+
+    /// # Safety
+    ///
+    /// This function is safe, it only stays `unsafe` for compatibility.
+    #[deprecated(note = "use the safe `MasterBootRecord::parse` instead")]
+    pub unsafe fn from_buf(buf: &[u8]) -> Result<Self> {
+        Self::parse(buf)
+    }
+
+    /// Create a protective MBR for a disk with `num_blocks` logical blocks.
+    pub fn new_protective(num_blocks: u64) -> Self {
+        Self {
+            bootstrapcode: [0; 440],
+            unique_mbr_signature: [0; 4],
+            unknown: [0; 2],
+            partition: [
+                MBRPartitionRecord::new_protective(num_blocks),
+                MBRPartitionRecord::EMPTY,
+                MBRPartitionRecord::EMPTY,
+                MBRPartitionRecord::EMPTY,
+            ],
+            signature: Self::SIGNATURE.to_le_bytes(),
+        }
+    }
+
+    /// Return the signature as u16
+    pub fn signature(&self) -> u16 {
+        u16::from_le_bytes(self.signature)
+    }
+
+    /// Boot signature at the end of every MBR.
+    pub const SIGNATURE: u16 = 0xaa55;
+
+    pub fn verify(&self, last_lba: Option<u32>) -> Result<()> {
+        if self.signature() != Self::SIGNATURE {
+            return Err(GPTError::InvalidData);
+        }
+
+        self.verify_partitions(last_lba)?;
+
+        Ok(())
+    }
+
+    /// Check that the used partition records don't overlap, and don't reach past `last_lba`.
+    ///
+    /// Fails with [`GPTError::OverlappingPartitions`] if two partitions share a block, and with
+    /// [`GPTError::InvalidMbr`] if a partition ends after `last_lba`.
+    pub fn verify_partitions(&self, last_lba: Option<u32>) -> Result<()> {
+        for (i, left) in self.partition.iter().enumerate() {
+            if left.is_empty() {
+                continue;
+            }
+
+            if let Some(last_lba) = last_lba {
+                if left.ending_lba() > last_lba {
+                    return Err(GPTError::InvalidMbr);
+                }
+            }
+
+            for right in self.partition[i + 1..]
+                .iter()
+                .filter(|part| !part.is_empty())
+            {
+                if left.starting_lba() < right.ending_lba()
+                    && right.starting_lba() < left.ending_lba()
+                {
+                    return Err(GPTError::OverlappingPartitions);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mbr::{Chs, Geometry, MBRPartitionRecord, MasterBootRecord};
+    use crate::GPTError;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<MBRPartitionRecord>(), 16);
+        assert_eq!(core::mem::size_of::<MasterBootRecord>(), 512)
+    }
+
+    #[test]
+    fn overlapping() {
+        let record = |start: u32, size: u32| MBRPartitionRecord {
+            os_indicator: 0x83,
+            starting_lba: start.to_le_bytes(),
+            size_in_lba: size.to_le_bytes(),
+            ..MBRPartitionRecord::EMPTY
+        };
+
+        let mut mbr = MasterBootRecord::new_protective(96);
+        mbr.partition[0] = record(1, 10);
+        mbr.partition[2] = record(11, 10);
+        assert!(mbr.verify_partitions(Some(96)).is_ok());
+        assert!(mbr.verify_partitions(Some(20)).is_err());
+
+        mbr.partition[3] = record(20, 5);
+        assert!(matches!(
+            mbr.verify_partitions(None),
+            Err(GPTError::OverlappingPartitions)
+        ));
+    }
+
+    #[test]
+    fn roundtrip_unaligned() {
+        let mbr = MasterBootRecord::new_protective(96);
+
+        // Start at an odd offset, to make sure the alignment doesn't matter.
+        let mut buf = [0u8; 513];
+        mbr.write_to(&mut buf[1..]).unwrap();
+        assert_eq!(buf[1 + 446 + 4], MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE);
+        assert_eq!(buf[1 + 446 + 12..1 + 446 + 16], 95u32.to_le_bytes());
+        assert_eq!(buf[511..513], [0x55, 0xaa]);
+
+        let parsed = MasterBootRecord::parse(&buf[1..]).unwrap();
+        assert_eq!(parsed.signature(), MasterBootRecord::SIGNATURE);
+        assert_eq!(parsed.partition[0].starting_lba(), 1);
+        assert_eq!(parsed.partition[0].size_in_lba(), 95);
+        assert!(parsed.partition[1].is_empty());
+
+        let mut again = [0u8; 512];
+        parsed.write_to(&mut again).unwrap();
+        assert_eq!(again[..], buf[1..]);
+
+        assert!(MasterBootRecord::parse(&buf[2..]).is_err());
+    }
+
+    #[test]
+    fn chs() {
+        let part = MBRPartitionRecord::new(0x83, 2048, 16_065);
+        assert_eq!(part.start_chs().to_lba(Geometry::DEFAULT), Some(2048));
+        assert_eq!(
+            part.end_chs().to_lba(Geometry::DEFAULT),
+            Some(2048 + 16_064)
+        );
+
+        let small = Geometry::new(16, 32).unwrap();
+        let mut part = MBRPartitionRecord::new_with_geometry(0x83, 2048, 1024, small);
+        assert_eq!(part.start_chs().to_lba(small), Some(2048));
+        assert_eq!(part.end_chs().to_lba(small), Some(3071));
+
+        part.set_lbas(u32::MAX - 1, 1);
+        assert_eq!(part.start_chs(), Chs::MAX);
+        assert_eq!(part.end_chs(), Chs::MAX);
+
+        let part = MBRPartitionRecord::new_protective(96);
+        assert_eq!(
+            (part.start_head, part.start_sector, part.start_track),
+            (0, 2, 0)
+        );
+        assert_eq!(part.end_chs().to_lba(Geometry::DEFAULT), Some(95));
+
+        // The last block of the CHS range is still representable.
+        let part = MBRPartitionRecord::new_protective(16_450_560);
+        assert_eq!(
+            (part.end_head, part.end_sector, part.end_track),
+            (254, 0xff, 0xff)
+        );
+        let part = MBRPartitionRecord::new_protective(16_450_561);
+        assert_eq!(
+            (part.end_head, part.end_sector, part.end_track),
+            (0xff, 0xff, 0xff)
+        );
+
+        let mut part = MBRPartitionRecord::new_protective(96);
+        part.set_protective_lbas(34, 16_450_526);
+        assert_eq!(part.end_chs(), Chs::MAX);
+        part.set_protective_lbas(34, 16_450_527);
+        assert_eq!(
+            (part.end_head, part.end_sector, part.end_track),
+            (0xff, 0xff, 0xff)
+        );
+
+        let part = MBRPartitionRecord::new_protective(u64::MAX);
+        assert_eq!(
+            (part.end_head, part.end_sector, part.end_track),
+            (0xff, 0xff, 0xff)
+        );
+    }
+}
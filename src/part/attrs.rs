@@ -1,8 +1,15 @@
-#[cfg(any(feature = "bitflags"))]
-use crate::GPTError;
+use crate::part::{DefaultGPTTypeGuid, GPTTypeGuid};
 
 #[cfg(any(feature = "bitflags"))]
 bitflags::bitflags! {
+    /// Attributes of a partition entry.
+    ///
+    /// Only the bits defined by the UEFI specification are flags. The reserved bits and the
+    /// type-specific bits 48-63 are kept by [`Attributes::from`], so writing an entry back
+    /// doesn't lose them. Most operations work on all 64 bits, but [`Attributes::complement`]
+    /// and `!` drop all bits which are not flags, and [`Attributes::from_bits`] rejects them.
+    /// Use [`Attributes::type_specific`] and [`Attributes::set_type_specific`] to access the
+    /// type-specific bits.
     #[repr(transparent)]
     pub struct Attributes: u64 {
         ///If this bit is set, the partition is required for the platform to function.
@@ -30,23 +37,29 @@ bitflags::bitflags! {
 }
 
 #[cfg(not(feature = "bitflags"))]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Attributes(pub u64);
 
 #[cfg(not(feature = "bitflags"))]
 impl Attributes {
+    /// Attributes with no bit set.
+    pub const fn empty() -> Self {
+        Attributes(0)
+    }
+
     fn bits(&self) -> u64 {
         self.0
     }
 }
 
 #[cfg(feature = "bitflags")]
-impl TryFrom<u64> for Attributes {
-    type Error = GPTError;
-
-    fn try_from(value: u64) -> Result<Self, Self::Error> {
-        Attributes::from_bits(value).ok_or(GPTError::InvalidData)
+impl From<u64> for Attributes {
+    /// Keeps all bits, including the reserved and type-specific ones not defined as flags.
+    fn from(value: u64) -> Self {
+        // SAFETY: There is no memory safety invariant on the bits. bitflags 1.x only marks this
+        // unsafe, as bits which are not flags are dropped by some operations, see `Attributes`.
+        unsafe { Attributes::from_bits_unchecked(value) }
     }
 }
 
@@ -62,3 +75,256 @@ impl From<Attributes> for u64 {
         attr.bits()
     }
 }
+
+impl Attributes {
+    /// First of the 16 bits whose meaning depends on the partition type.
+    const TYPE_SPECIFIC_SHIFT: u32 = 48;
+
+    /// The 16 bits (48-63) whose meaning depends on the partition type.
+    pub fn type_specific(&self) -> u16 {
+        (u64::from(*self) >> Self::TYPE_SPECIFIC_SHIFT) as u16
+    }
+
+    /// Replace the 16 type-specific bits (48-63), keeping all other bits.
+    pub fn set_type_specific(&mut self, bits: u16) {
+        let mask = !(0xffffu64 << Self::TYPE_SPECIFIC_SHIFT);
+        let value = (u64::from(*self) & mask) | (bits as u64) << Self::TYPE_SPECIFIC_SHIFT;
+        *self = Attributes::from(value);
+    }
+
+    /// Decode the type-specific bits according to the partition type `type_guid`.
+    pub fn type_attributes<T: GPTTypeGuid + Clone>(&self, type_guid: &T) -> TypeAttributes {
+        let bits = self.type_specific();
+        let kind = match type_guid.as_guid() {
+            Some(guid) => DefaultGPTTypeGuid::from(guid),
+            None => return TypeAttributes::Unknown(bits),
+        };
+
+        match kind {
+            DefaultGPTTypeGuid::MicrosoftBasicData => {
+                TypeAttributes::Microsoft(MicrosoftAttributes(bits))
+            }
+            DefaultGPTTypeGuid::ChromeOSKernel => {
+                TypeAttributes::ChromeOS(ChromeOSAttributes(bits))
+            }
+            DefaultGPTTypeGuid::ESP
+            | DefaultGPTTypeGuid::XBootLdr
+            | DefaultGPTTypeGuid::LinuxRoot(_)
+            | DefaultGPTTypeGuid::LinuxUsr(_)
+            | DefaultGPTTypeGuid::LinuxRootVerity(_)
+            | DefaultGPTTypeGuid::LinuxUsrVerity(_)
+            | DefaultGPTTypeGuid::LinuxHome
+            | DefaultGPTTypeGuid::LinuxSrv
+            | DefaultGPTTypeGuid::LinuxVar
+            | DefaultGPTTypeGuid::LinuxVarTmp
+            | DefaultGPTTypeGuid::LinuxSwap => TypeAttributes::Systemd(SystemdAttributes(bits)),
+            _ => TypeAttributes::Unknown(bits),
+        }
+    }
+}
+
+/// Type-specific attribute bits, decoded according to the partition type by
+/// [`Attributes::type_attributes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeAttributes {
+    /// Microsoft basic data partition.
+    Microsoft(MicrosoftAttributes),
+    /// ChromeOS kernel partition.
+    ChromeOS(ChromeOSAttributes),
+    /// Partition types of the discoverable partitions specification used by systemd.
+    Systemd(SystemdAttributes),
+    /// The meaning of the bits is not known for the partition type.
+    Unknown(u16),
+}
+
+macro_rules! type_attribute_bit {
+    ($get:ident, $set:ident, $bit:expr, $doc:expr) => {
+        #[doc = $doc]
+        pub const fn $get(&self) -> bool {
+            self.0 & (1 << ($bit - 48)) != 0
+        }
+
+        #[doc = $doc]
+        pub fn $set(&mut self, value: bool) {
+            if value {
+                self.0 |= 1 << ($bit - 48);
+            } else {
+                self.0 &= !(1 << ($bit - 48));
+            }
+        }
+    };
+}
+
+/// Type-specific attributes of Microsoft basic data partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MicrosoftAttributes(pub u16);
+
+impl MicrosoftAttributes {
+    type_attribute_bit!(
+        read_only,
+        set_read_only,
+        60,
+        "The partition is read-only (bit 60)."
+    );
+    type_attribute_bit!(
+        shadow_copy,
+        set_shadow_copy,
+        61,
+        "The partition is a shadow copy (bit 61)."
+    );
+    type_attribute_bit!(hidden, set_hidden, 62, "The partition is hidden (bit 62).");
+    type_attribute_bit!(
+        no_drive_letter,
+        set_no_drive_letter,
+        63,
+        "No drive letter is assigned to the partition (bit 63)."
+    );
+}
+
+/// Type-specific attributes of ChromeOS kernel partitions, used to select the kernel to boot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChromeOSAttributes(pub u16);
+
+impl ChromeOSAttributes {
+    /// Highest priority and number of tries.
+    pub const MAX: u8 = 15;
+
+    /// Boot priority (bits 48-51). 0 means not bootable, 15 is the highest priority.
+    pub const fn priority(&self) -> u8 {
+        (self.0 & 0xf) as u8
+    }
+
+    /// Set the boot priority, saturating at [`Self::MAX`].
+    pub fn set_priority(&mut self, priority: u8) {
+        let priority = if priority > Self::MAX {
+            Self::MAX
+        } else {
+            priority
+        };
+        self.0 = (self.0 & !0xf) | priority as u16;
+    }
+
+    /// Remaining boot attempts (bits 52-55).
+    pub const fn tries(&self) -> u8 {
+        ((self.0 >> 4) & 0xf) as u8
+    }
+
+    /// Set the remaining boot attempts, saturating at [`Self::MAX`].
+    pub fn set_tries(&mut self, tries: u8) {
+        let tries = if tries > Self::MAX { Self::MAX } else { tries };
+        self.0 = (self.0 & !0xf0) | (tries as u16) << 4;
+    }
+
+    type_attribute_bit!(
+        successful,
+        set_successful,
+        56,
+        "The kernel booted successfully before (bit 56)."
+    );
+}
+
+/// Type-specific attributes of the discoverable partitions specification used by systemd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemdAttributes(pub u16);
+
+impl SystemdAttributes {
+    type_attribute_bit!(
+        grow_fs,
+        set_grow_fs,
+        59,
+        "The file system is grown to the size of the partition on mount (bit 59)."
+    );
+    type_attribute_bit!(
+        read_only,
+        set_read_only,
+        60,
+        "The partition is mounted read-only (bit 60)."
+    );
+    type_attribute_bit!(
+        no_auto,
+        set_no_auto,
+        63,
+        "The partition is not mounted automatically (bit 63)."
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Attributes, ChromeOSAttributes, TypeAttributes};
+    use crate::part::{Arch, DefaultGPTTypeGuid};
+    use crate::GUID;
+
+    #[test]
+    fn keep_unknown_bits() {
+        let bits = 0x9000_0000_0000_0005;
+        let mut attrs = Attributes::from(bits);
+        assert_eq!(u64::from(attrs), bits);
+        assert_eq!(attrs.type_specific(), 0x9000);
+
+        attrs.set_type_specific(0x0102);
+        assert_eq!(u64::from(attrs), 0x0102_0000_0000_0005);
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn bitflags_unknown_bits() {
+        let attrs = Attributes::from(0x9000_0000_0000_0005);
+        assert_eq!(
+            u64::from(attrs | Attributes::NO_BLOCK_IO),
+            0x9000_0000_0000_0007
+        );
+        assert_eq!(
+            u64::from(attrs - Attributes::REQUIRED),
+            0x9000_0000_0000_0004
+        );
+
+        // Documented to drop the bits which are not flags.
+        assert_eq!(attrs.complement(), Attributes::NO_BLOCK_IO);
+        assert!(Attributes::from_bits(u64::from(attrs)).is_none());
+    }
+
+    #[test]
+    fn type_attributes() {
+        let attrs = Attributes::from(0x9000_0000_0000_0000);
+
+        match attrs.type_attributes(&DefaultGPTTypeGuid::MicrosoftBasicData) {
+            TypeAttributes::Microsoft(ms) => {
+                assert!(ms.read_only() && ms.no_drive_letter());
+                assert!(!ms.hidden() && !ms.shadow_copy());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        match attrs.type_attributes(&DefaultGPTTypeGuid::LinuxRoot(Arch::X86_64)) {
+            TypeAttributes::Systemd(sd) => {
+                assert!(sd.read_only() && sd.no_auto() && !sd.grow_fs());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert_eq!(
+            attrs.type_attributes(&GUID::LEGACY_MBR),
+            TypeAttributes::Unknown(0x9000)
+        );
+    }
+
+    #[test]
+    fn chromeos() {
+        let mut cros = ChromeOSAttributes::default();
+        cros.set_priority(20);
+        cros.set_tries(3);
+        cros.set_successful(true);
+        assert_eq!(
+            (cros.priority(), cros.tries(), cros.successful()),
+            (15, 3, true)
+        );
+        assert_eq!(cros.0, 0x013f);
+
+        let mut attrs = Attributes::from(1);
+        attrs.set_type_specific(cros.0);
+        assert_eq!(
+            attrs.type_attributes(&DefaultGPTTypeGuid::ChromeOSKernel),
+            TypeAttributes::ChromeOS(cros)
+        );
+    }
+}
@@ -1,10 +1,21 @@
 use crate::{read_le_bytes, GPTError, Result, GUID};
 
 mod attrs;
-pub use attrs::Attributes;
+pub use attrs::{
+    Attributes, ChromeOSAttributes, MicrosoftAttributes, SystemdAttributes, TypeAttributes,
+};
+mod types;
+pub use types::{Arch, OsFamily, PartitionType};
+
+/// Derive [`GPTTypeGuid`] for custom partition type enums.
+#[cfg(feature = "derive")]
+pub use nogpt_derive::GptTypeGuid;
 
 //pub const ESP_GUID_TYPE: GUID = GUID::new()
 
+/// Partition entry with the raw type guid and attribute bits, used when changing entries.
+pub(crate) type RawPartHeader = GPTPartHeader<GUID, u64>;
+
 pub struct GPTPartHeader<T = DefaultGPTTypeGuid, A = Attributes>
 where
     T: GPTTypeGuid,
@@ -84,6 +95,93 @@ where
     }
 }
 
+impl<T, A> GPTPartHeader<T, A>
+where
+    T: GPTTypeGuid,
+{
+    /// Size in bytes of the fields of a partition entry defined by `UEFI`.
+    pub const SIZE: usize = 128;
+
+    /// Create a new partition entry without a name.
+    pub fn new(type_guid: T, guid: GUID, start_lba: u64, end_lba: u64, attrs: A) -> Self {
+        Self {
+            type_guid,
+            guid,
+
+            start_lba,
+            end_lba,
+
+            attrs,
+
+            name: [0; 36],
+
+            #[cfg(feature = "alloc")]
+            name_str: alloc::string::String::new(),
+        }
+    }
+
+    /// Compare the name of the partition with `name`, without allocations.
+    pub fn name_eq(&self, name: &str) -> bool {
+        self.name
+            .iter()
+            .take_while(|&&c| c != 0)
+            .copied()
+            .eq(name.encode_utf16())
+    }
+
+    /// Set the name of the partition, encoded as UTF-16.
+    ///
+    /// Fails with [`GPTError::InvalidData`] if the name does not fit into 36 UTF-16 code units.
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        let mut encoded = [0; 36];
+        for (i, c) in name.encode_utf16().enumerate() {
+            *encoded.get_mut(i).ok_or(GPTError::InvalidData)? = c;
+        }
+        self.name = encoded;
+
+        #[cfg(feature = "alloc")]
+        {
+            self.name_str = alloc::string::String::from(name);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, A> GPTPartHeader<T, A>
+where
+    T: GPTTypeGuid + Clone,
+    GPTError: From<<T as TryInto<[u8; 16]>>::Error>,
+    A: TryInto<u64> + Clone,
+    GPTError: From<<A as TryInto<u64>>::Error>,
+{
+    /// Serialize the partition entry into `buf`, as it is laid out on disk.
+    ///
+    /// Only the first 128 bytes are written. If the partition entries are bigger, the reserved
+    /// bytes following the entry are kept as they are in `buf`.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() < Self::SIZE {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        let type_guid: [u8; 16] = self.type_guid.clone().try_into()?;
+        buf[0..16].copy_from_slice(&type_guid);
+        buf[16..32].copy_from_slice(&self.guid.as_bytes());
+
+        buf[32..40].copy_from_slice(&self.start_lba.to_le_bytes());
+        buf[40..48].copy_from_slice(&self.end_lba.to_le_bytes());
+
+        let attrs: u64 = self.attrs.clone().try_into()?;
+        buf[48..56].copy_from_slice(&attrs.to_le_bytes());
+
+        for (x, c) in self.name.iter().enumerate() {
+            buf[56 + x * 2..58 + x * 2].copy_from_slice(&c.to_le_bytes());
+        }
+
+        Ok(())
+    }
+}
+
 impl<T, A> core::fmt::Debug for GPTPartHeader<T, A>
 where
     T: GPTTypeGuid + core::fmt::Debug,
@@ -117,7 +215,74 @@ where
 }
 
 pub trait GPTTypeGuid: TryFrom<[u8; 16]> + TryInto<[u8; 16]> {
-    // TODO: add provided function to convert to guid values pretty printed string
+    /// The type guid as stored in the partition entry, if it can be converted.
+    fn as_guid(&self) -> Option<GUID>
+    where
+        Self: Clone,
+    {
+        let guid: [u8; 16] = self.clone().try_into().ok()?;
+        Some(GUID::from(guid))
+    }
+
+    /// Human-readable name of the partition type, if it is known.
+    fn name(&self) -> Option<&'static str>
+    where
+        Self: Clone,
+    {
+        PartitionType::from_guid(self.as_guid()?).map(|t| t.name)
+    }
+
+    /// Operating system using the partition type, if it is known.
+    fn os(&self) -> Option<OsFamily>
+    where
+        Self: Clone,
+    {
+        PartitionType::from_guid(self.as_guid()?).map(|t| t.os)
+    }
+
+    /// CPU architecture of the partition type, for the architecture specific Linux root, `/usr`
+    /// and dm-verity types.
+    fn arch(&self) -> Option<Arch>
+    where
+        Self: Clone,
+    {
+        match DefaultGPTTypeGuid::from(self.as_guid()?) {
+            DefaultGPTTypeGuid::LinuxRoot(arch)
+            | DefaultGPTTypeGuid::LinuxUsr(arch)
+            | DefaultGPTTypeGuid::LinuxRootVerity(arch)
+            | DefaultGPTTypeGuid::LinuxUsrVerity(arch) => Some(arch),
+            _ => None,
+        }
+    }
+
+    /// Check if the type marks an unused partition entry.
+    fn is_unused(&self) -> bool
+    where
+        Self: Clone,
+    {
+        self.as_guid() == Some(GUID::UNUSED)
+    }
+
+    /// Display the name of the partition type, or its guid if the name is unknown.
+    fn display(&self) -> TypeGuidDisplay<'_, Self>
+    where
+        Self: Clone,
+    {
+        TypeGuidDisplay(self)
+    }
+}
+
+/// Display helper for [`GPTTypeGuid`], returned by [`GPTTypeGuid::display`].
+pub struct TypeGuidDisplay<'a, T: GPTTypeGuid + Clone>(&'a T);
+
+impl<'a, T: GPTTypeGuid + Clone> core::fmt::Display for TypeGuidDisplay<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.0.name(), self.0.as_guid()) {
+            (Some(name), _) => f.write_str(name),
+            (None, Some(guid)) => core::fmt::Display::fmt(&guid, f),
+            (None, None) => f.write_str("invalid type guid"),
+        }
+    }
 }
 
 // TODO: somehow pack to same size as GUID
@@ -130,6 +295,116 @@ pub enum DefaultGPTTypeGuid {
     ESP,
     /// Partition containing a legacy MBR
     LegacyMBR,
+    /// BIOS boot partition, used by GRUB on BIOS systems.
+    BIOSBoot,
+    /// Extended boot loader partition (`XBOOTLDR`) of the boot loader specification.
+    XBootLdr,
+    /// Linux filesystem data.
+    LinuxFilesystem,
+    /// Linux `/home` partition.
+    LinuxHome,
+    /// Linux `/srv` partition.
+    LinuxSrv,
+    /// Linux `/var` partition.
+    LinuxVar,
+    /// Linux `/var/tmp` partition.
+    LinuxVarTmp,
+    /// Linux swap space.
+    LinuxSwap,
+    /// Linux logical volume manager physical volume.
+    LinuxLVM,
+    /// Linux software RAID member.
+    LinuxRAID,
+    /// Linux LUKS encrypted partition.
+    LinuxLUKS,
+    /// Linux plain dm-crypt partition.
+    LinuxDmCrypt,
+    /// Reserved for Linux.
+    LinuxReserved,
+    /// Microsoft reserved partition (MSR).
+    MicrosoftReserved,
+    /// Microsoft basic data partition, also used for FAT and exFAT on other systems.
+    MicrosoftBasicData,
+    /// Windows recovery environment.
+    WindowsRecovery,
+    /// Logical disk manager metadata partition.
+    WindowsLDMMetadata,
+    /// Logical disk manager data partition.
+    WindowsLDMData,
+    /// Windows Storage Spaces partition.
+    WindowsStorageSpaces,
+    /// Apple HFS+ partition.
+    AppleHFSPlus,
+    /// Apple APFS container.
+    AppleAPFS,
+    /// Apple UFS partition.
+    AppleUFS,
+    /// Apple RAID partition.
+    AppleRAID,
+    /// Apple boot partition (recovery HD).
+    AppleBoot,
+    /// Apple Core Storage container.
+    AppleCoreStorage,
+    /// FreeBSD boot partition.
+    FreeBSDBoot,
+    /// FreeBSD disklabel partition.
+    FreeBSDData,
+    /// FreeBSD swap partition.
+    FreeBSDSwap,
+    /// FreeBSD UFS partition.
+    FreeBSDUFS,
+    /// FreeBSD Vinum volume manager partition.
+    FreeBSDVinum,
+    /// FreeBSD ZFS partition.
+    FreeBSDZFS,
+    /// ChromeOS kernel partition.
+    ChromeOSKernel,
+    /// ChromeOS root filesystem.
+    ChromeOSRootfs,
+    /// ChromeOS firmware partition.
+    ChromeOSFirmware,
+    /// ChromeOS partition reserved for future use.
+    ChromeOSReserved,
+    /// ChromeOS miniOS partition.
+    ChromeOSMiniOS,
+    /// ChromeOS hibernate partition.
+    ChromeOSHibernate,
+    /// Android bootloader partition.
+    AndroidBootloader,
+    /// Second Android bootloader partition.
+    AndroidBootloader2,
+    /// Android boot image.
+    AndroidBoot,
+    /// Android recovery image.
+    AndroidRecovery,
+    /// Android misc partition.
+    AndroidMisc,
+    /// Android metadata partition.
+    AndroidMetadata,
+    /// Android system partition.
+    AndroidSystem,
+    /// Android cache partition.
+    AndroidCache,
+    /// Android user data partition.
+    AndroidData,
+    /// Android persistent partition.
+    AndroidPersistent,
+    /// Android vendor partition.
+    AndroidVendor,
+    /// Android config partition.
+    AndroidConfig,
+    /// Android factory partition.
+    AndroidFactory,
+    /// Android OEM partition.
+    AndroidOEM,
+    /// Linux root partition for the given architecture.
+    LinuxRoot(Arch),
+    /// Linux `/usr` partition for the given architecture.
+    LinuxUsr(Arch),
+    /// Linux dm-verity hash partition of the root partition for the given architecture.
+    LinuxRootVerity(Arch),
+    /// Linux dm-verity hash partition of the `/usr` partition for the given architecture.
+    LinuxUsrVerity(Arch),
     Unknown(GUID),
 }
 
@@ -153,6 +428,61 @@ impl From<DefaultGPTTypeGuid> for GUID {
             DefaultGPTTypeGuid::Unused => GUID::UNUSED,
             DefaultGPTTypeGuid::ESP => GUID::ESP,
             DefaultGPTTypeGuid::LegacyMBR => GUID::LEGACY_MBR,
+            DefaultGPTTypeGuid::BIOSBoot => GUID::BIOS_BOOT,
+            DefaultGPTTypeGuid::XBootLdr => GUID::XBOOTLDR,
+            DefaultGPTTypeGuid::LinuxFilesystem => GUID::LINUX_FS,
+            DefaultGPTTypeGuid::LinuxHome => GUID::LINUX_HOME,
+            DefaultGPTTypeGuid::LinuxSrv => GUID::LINUX_SRV,
+            DefaultGPTTypeGuid::LinuxVar => GUID::LINUX_VAR,
+            DefaultGPTTypeGuid::LinuxVarTmp => GUID::LINUX_VAR_TMP,
+            DefaultGPTTypeGuid::LinuxSwap => GUID::LINUX_SWAP,
+            DefaultGPTTypeGuid::LinuxLVM => GUID::LINUX_LVM,
+            DefaultGPTTypeGuid::LinuxRAID => GUID::LINUX_RAID,
+            DefaultGPTTypeGuid::LinuxLUKS => GUID::LINUX_LUKS,
+            DefaultGPTTypeGuid::LinuxDmCrypt => GUID::LINUX_DM_CRYPT,
+            DefaultGPTTypeGuid::LinuxReserved => GUID::LINUX_RESERVED,
+            DefaultGPTTypeGuid::MicrosoftReserved => GUID::MICROSOFT_RESERVED,
+            DefaultGPTTypeGuid::MicrosoftBasicData => GUID::MICROSOFT_BASIC_DATA,
+            DefaultGPTTypeGuid::WindowsRecovery => GUID::WINDOWS_RECOVERY,
+            DefaultGPTTypeGuid::WindowsLDMMetadata => GUID::WINDOWS_LDM_METADATA,
+            DefaultGPTTypeGuid::WindowsLDMData => GUID::WINDOWS_LDM_DATA,
+            DefaultGPTTypeGuid::WindowsStorageSpaces => GUID::WINDOWS_STORAGE_SPACES,
+            DefaultGPTTypeGuid::AppleHFSPlus => GUID::APPLE_HFS_PLUS,
+            DefaultGPTTypeGuid::AppleAPFS => GUID::APPLE_APFS,
+            DefaultGPTTypeGuid::AppleUFS => GUID::APPLE_UFS,
+            DefaultGPTTypeGuid::AppleRAID => GUID::APPLE_RAID,
+            DefaultGPTTypeGuid::AppleBoot => GUID::APPLE_BOOT,
+            DefaultGPTTypeGuid::AppleCoreStorage => GUID::APPLE_CORE_STORAGE,
+            DefaultGPTTypeGuid::FreeBSDBoot => GUID::FREEBSD_BOOT,
+            DefaultGPTTypeGuid::FreeBSDData => GUID::FREEBSD_DATA,
+            DefaultGPTTypeGuid::FreeBSDSwap => GUID::FREEBSD_SWAP,
+            DefaultGPTTypeGuid::FreeBSDUFS => GUID::FREEBSD_UFS,
+            DefaultGPTTypeGuid::FreeBSDVinum => GUID::FREEBSD_VINUM,
+            DefaultGPTTypeGuid::FreeBSDZFS => GUID::FREEBSD_ZFS,
+            DefaultGPTTypeGuid::ChromeOSKernel => GUID::CHROMEOS_KERNEL,
+            DefaultGPTTypeGuid::ChromeOSRootfs => GUID::CHROMEOS_ROOTFS,
+            DefaultGPTTypeGuid::ChromeOSFirmware => GUID::CHROMEOS_FIRMWARE,
+            DefaultGPTTypeGuid::ChromeOSReserved => GUID::CHROMEOS_RESERVED,
+            DefaultGPTTypeGuid::ChromeOSMiniOS => GUID::CHROMEOS_MINIOS,
+            DefaultGPTTypeGuid::ChromeOSHibernate => GUID::CHROMEOS_HIBERNATE,
+            DefaultGPTTypeGuid::AndroidBootloader => GUID::ANDROID_BOOTLOADER,
+            DefaultGPTTypeGuid::AndroidBootloader2 => GUID::ANDROID_BOOTLOADER2,
+            DefaultGPTTypeGuid::AndroidBoot => GUID::ANDROID_BOOT,
+            DefaultGPTTypeGuid::AndroidRecovery => GUID::ANDROID_RECOVERY,
+            DefaultGPTTypeGuid::AndroidMisc => GUID::ANDROID_MISC,
+            DefaultGPTTypeGuid::AndroidMetadata => GUID::ANDROID_METADATA,
+            DefaultGPTTypeGuid::AndroidSystem => GUID::ANDROID_SYSTEM,
+            DefaultGPTTypeGuid::AndroidCache => GUID::ANDROID_CACHE,
+            DefaultGPTTypeGuid::AndroidData => GUID::ANDROID_DATA,
+            DefaultGPTTypeGuid::AndroidPersistent => GUID::ANDROID_PERSISTENT,
+            DefaultGPTTypeGuid::AndroidVendor => GUID::ANDROID_VENDOR,
+            DefaultGPTTypeGuid::AndroidConfig => GUID::ANDROID_CONFIG,
+            DefaultGPTTypeGuid::AndroidFactory => GUID::ANDROID_FACTORY,
+            DefaultGPTTypeGuid::AndroidOEM => GUID::ANDROID_OEM,
+            DefaultGPTTypeGuid::LinuxRoot(arch) => arch.root(),
+            DefaultGPTTypeGuid::LinuxUsr(arch) => arch.usr(),
+            DefaultGPTTypeGuid::LinuxRootVerity(arch) => arch.root_verity(),
+            DefaultGPTTypeGuid::LinuxUsrVerity(arch) => arch.usr_verity(),
             DefaultGPTTypeGuid::Unknown(v) => v,
         }
     }
@@ -164,6 +494,93 @@ impl From<GUID> for DefaultGPTTypeGuid {
             GUID::UNUSED => DefaultGPTTypeGuid::Unused,
             GUID::ESP => DefaultGPTTypeGuid::ESP,
             GUID::LEGACY_MBR => DefaultGPTTypeGuid::LegacyMBR,
+            GUID::BIOS_BOOT => DefaultGPTTypeGuid::BIOSBoot,
+            GUID::XBOOTLDR => DefaultGPTTypeGuid::XBootLdr,
+            GUID::LINUX_FS => DefaultGPTTypeGuid::LinuxFilesystem,
+            GUID::LINUX_HOME => DefaultGPTTypeGuid::LinuxHome,
+            GUID::LINUX_SRV => DefaultGPTTypeGuid::LinuxSrv,
+            GUID::LINUX_VAR => DefaultGPTTypeGuid::LinuxVar,
+            GUID::LINUX_VAR_TMP => DefaultGPTTypeGuid::LinuxVarTmp,
+            GUID::LINUX_SWAP => DefaultGPTTypeGuid::LinuxSwap,
+            GUID::LINUX_LVM => DefaultGPTTypeGuid::LinuxLVM,
+            GUID::LINUX_RAID => DefaultGPTTypeGuid::LinuxRAID,
+            GUID::LINUX_LUKS => DefaultGPTTypeGuid::LinuxLUKS,
+            GUID::LINUX_DM_CRYPT => DefaultGPTTypeGuid::LinuxDmCrypt,
+            GUID::LINUX_RESERVED => DefaultGPTTypeGuid::LinuxReserved,
+            GUID::MICROSOFT_RESERVED => DefaultGPTTypeGuid::MicrosoftReserved,
+            GUID::MICROSOFT_BASIC_DATA => DefaultGPTTypeGuid::MicrosoftBasicData,
+            GUID::WINDOWS_RECOVERY => DefaultGPTTypeGuid::WindowsRecovery,
+            GUID::WINDOWS_LDM_METADATA => DefaultGPTTypeGuid::WindowsLDMMetadata,
+            GUID::WINDOWS_LDM_DATA => DefaultGPTTypeGuid::WindowsLDMData,
+            GUID::WINDOWS_STORAGE_SPACES => DefaultGPTTypeGuid::WindowsStorageSpaces,
+            GUID::APPLE_HFS_PLUS => DefaultGPTTypeGuid::AppleHFSPlus,
+            GUID::APPLE_APFS => DefaultGPTTypeGuid::AppleAPFS,
+            GUID::APPLE_UFS => DefaultGPTTypeGuid::AppleUFS,
+            GUID::APPLE_RAID => DefaultGPTTypeGuid::AppleRAID,
+            GUID::APPLE_BOOT => DefaultGPTTypeGuid::AppleBoot,
+            GUID::APPLE_CORE_STORAGE => DefaultGPTTypeGuid::AppleCoreStorage,
+            GUID::FREEBSD_BOOT => DefaultGPTTypeGuid::FreeBSDBoot,
+            GUID::FREEBSD_DATA => DefaultGPTTypeGuid::FreeBSDData,
+            GUID::FREEBSD_SWAP => DefaultGPTTypeGuid::FreeBSDSwap,
+            GUID::FREEBSD_UFS => DefaultGPTTypeGuid::FreeBSDUFS,
+            GUID::FREEBSD_VINUM => DefaultGPTTypeGuid::FreeBSDVinum,
+            GUID::FREEBSD_ZFS => DefaultGPTTypeGuid::FreeBSDZFS,
+            GUID::CHROMEOS_KERNEL => DefaultGPTTypeGuid::ChromeOSKernel,
+            GUID::CHROMEOS_ROOTFS => DefaultGPTTypeGuid::ChromeOSRootfs,
+            GUID::CHROMEOS_FIRMWARE => DefaultGPTTypeGuid::ChromeOSFirmware,
+            GUID::CHROMEOS_RESERVED => DefaultGPTTypeGuid::ChromeOSReserved,
+            GUID::CHROMEOS_MINIOS => DefaultGPTTypeGuid::ChromeOSMiniOS,
+            GUID::CHROMEOS_HIBERNATE => DefaultGPTTypeGuid::ChromeOSHibernate,
+            GUID::ANDROID_BOOTLOADER => DefaultGPTTypeGuid::AndroidBootloader,
+            GUID::ANDROID_BOOTLOADER2 => DefaultGPTTypeGuid::AndroidBootloader2,
+            GUID::ANDROID_BOOT => DefaultGPTTypeGuid::AndroidBoot,
+            GUID::ANDROID_RECOVERY => DefaultGPTTypeGuid::AndroidRecovery,
+            GUID::ANDROID_MISC => DefaultGPTTypeGuid::AndroidMisc,
+            GUID::ANDROID_METADATA => DefaultGPTTypeGuid::AndroidMetadata,
+            GUID::ANDROID_SYSTEM => DefaultGPTTypeGuid::AndroidSystem,
+            GUID::ANDROID_CACHE => DefaultGPTTypeGuid::AndroidCache,
+            GUID::ANDROID_DATA => DefaultGPTTypeGuid::AndroidData,
+            GUID::ANDROID_PERSISTENT => DefaultGPTTypeGuid::AndroidPersistent,
+            GUID::ANDROID_VENDOR => DefaultGPTTypeGuid::AndroidVendor,
+            GUID::ANDROID_CONFIG => DefaultGPTTypeGuid::AndroidConfig,
+            GUID::ANDROID_FACTORY => DefaultGPTTypeGuid::AndroidFactory,
+            GUID::ANDROID_OEM => DefaultGPTTypeGuid::AndroidOEM,
+            GUID::LINUX_ROOT_X86 => DefaultGPTTypeGuid::LinuxRoot(Arch::X86),
+            GUID::LINUX_ROOT_X86_64 => DefaultGPTTypeGuid::LinuxRoot(Arch::X86_64),
+            GUID::LINUX_ROOT_ARM => DefaultGPTTypeGuid::LinuxRoot(Arch::Arm),
+            GUID::LINUX_ROOT_ARM64 => DefaultGPTTypeGuid::LinuxRoot(Arch::Arm64),
+            GUID::LINUX_ROOT_IA64 => DefaultGPTTypeGuid::LinuxRoot(Arch::Ia64),
+            GUID::LINUX_ROOT_LOONGARCH64 => DefaultGPTTypeGuid::LinuxRoot(Arch::LoongArch64),
+            GUID::LINUX_ROOT_RISCV32 => DefaultGPTTypeGuid::LinuxRoot(Arch::RiscV32),
+            GUID::LINUX_ROOT_RISCV64 => DefaultGPTTypeGuid::LinuxRoot(Arch::RiscV64),
+            GUID::LINUX_USR_X86 => DefaultGPTTypeGuid::LinuxUsr(Arch::X86),
+            GUID::LINUX_USR_X86_64 => DefaultGPTTypeGuid::LinuxUsr(Arch::X86_64),
+            GUID::LINUX_USR_ARM => DefaultGPTTypeGuid::LinuxUsr(Arch::Arm),
+            GUID::LINUX_USR_ARM64 => DefaultGPTTypeGuid::LinuxUsr(Arch::Arm64),
+            GUID::LINUX_USR_IA64 => DefaultGPTTypeGuid::LinuxUsr(Arch::Ia64),
+            GUID::LINUX_USR_LOONGARCH64 => DefaultGPTTypeGuid::LinuxUsr(Arch::LoongArch64),
+            GUID::LINUX_USR_RISCV32 => DefaultGPTTypeGuid::LinuxUsr(Arch::RiscV32),
+            GUID::LINUX_USR_RISCV64 => DefaultGPTTypeGuid::LinuxUsr(Arch::RiscV64),
+            GUID::LINUX_ROOT_VERITY_X86 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::X86),
+            GUID::LINUX_ROOT_VERITY_X86_64 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::X86_64),
+            GUID::LINUX_ROOT_VERITY_ARM => DefaultGPTTypeGuid::LinuxRootVerity(Arch::Arm),
+            GUID::LINUX_ROOT_VERITY_ARM64 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::Arm64),
+            GUID::LINUX_ROOT_VERITY_IA64 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::Ia64),
+            GUID::LINUX_ROOT_VERITY_LOONGARCH64 => {
+                DefaultGPTTypeGuid::LinuxRootVerity(Arch::LoongArch64)
+            }
+            GUID::LINUX_ROOT_VERITY_RISCV32 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::RiscV32),
+            GUID::LINUX_ROOT_VERITY_RISCV64 => DefaultGPTTypeGuid::LinuxRootVerity(Arch::RiscV64),
+            GUID::LINUX_USR_VERITY_X86 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::X86),
+            GUID::LINUX_USR_VERITY_X86_64 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::X86_64),
+            GUID::LINUX_USR_VERITY_ARM => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Arm),
+            GUID::LINUX_USR_VERITY_ARM64 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Arm64),
+            GUID::LINUX_USR_VERITY_IA64 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Ia64),
+            GUID::LINUX_USR_VERITY_LOONGARCH64 => {
+                DefaultGPTTypeGuid::LinuxUsrVerity(Arch::LoongArch64)
+            }
+            GUID::LINUX_USR_VERITY_RISCV32 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::RiscV32),
+            GUID::LINUX_USR_VERITY_RISCV64 => DefaultGPTTypeGuid::LinuxUsrVerity(Arch::RiscV64),
             v => DefaultGPTTypeGuid::Unknown(v),
         }
     }
@@ -172,9 +589,97 @@ impl From<GUID> for DefaultGPTTypeGuid {
 impl GPTTypeGuid for DefaultGPTTypeGuid {}
 impl GPTTypeGuid for GUID {}
 
+impl core::fmt::Display for DefaultGPTTypeGuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.display(), f)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{DefaultGPTTypeGuid, GUID};
+    use super::{Arch, Attributes, DefaultGPTTypeGuid, GPTPartHeader, GPTTypeGuid, OsFamily, GUID};
+
+    #[test]
+    fn write_parse() {
+        let attrs = Attributes::empty();
+        let mut part: GPTPartHeader =
+            GPTPartHeader::new(DefaultGPTTypeGuid::ESP, GUID::LEGACY_MBR, 34, 62, attrs);
+        part.set_name("EFI system partition").unwrap();
+
+        let mut buf = [0xffu8; 256];
+        part.write_to(&mut buf).unwrap();
+        assert_eq!(buf[128..], [0xffu8; 128][..]);
+
+        let parsed: GPTPartHeader = GPTPartHeader::parse(&buf).unwrap();
+        assert_eq!(parsed.type_guid, DefaultGPTTypeGuid::ESP);
+        assert_eq!(parsed.guid, GUID::LEGACY_MBR);
+        assert_eq!(parsed.start_lba, 34);
+        assert_eq!(parsed.end_lba, 62);
+        assert_eq!(parsed.name, part.name);
+    }
+
+    #[test]
+    fn set_name() {
+        let attrs = Attributes::empty();
+        let mut part: GPTPartHeader =
+            GPTPartHeader::new(DefaultGPTTypeGuid::Unused, GUID::UNUSED, 0, 0, attrs);
+
+        part.set_name("abcdefghijklmnopqrstuvwxyz0123456789")
+            .unwrap();
+        assert_eq!(part.name[35], '9' as u16);
+        assert!(part.name_eq("abcdefghijklmnopqrstuvwxyz0123456789"));
+        assert!(!part.name_eq("abcdefghijklmnopqrstuvwxyz012345678"));
+        part.set_name("abcdefghijklmnopqrstuvwxyz0123456789_")
+            .expect_err("name is longer than 36 code units");
+    }
+
+    /// Implementations of [`GPTTypeGuid`] don't have to be `Clone`.
+    struct Opaque([u8; 16]);
+
+    impl From<[u8; 16]> for Opaque {
+        fn from(guid: [u8; 16]) -> Self {
+            Opaque(guid)
+        }
+    }
+
+    impl From<Opaque> for [u8; 16] {
+        fn from(guid: Opaque) -> Self {
+            guid.0
+        }
+    }
+
+    impl GPTTypeGuid for Opaque {}
+
+    #[test]
+    fn without_clone() {
+        let mut buf = [0u8; 128];
+        let entry: GPTPartHeader<GUID, u64> =
+            GPTPartHeader::new(GUID::LINUX_FS, GUID::ESP, 34, 62, 0);
+        entry.write_to(&mut buf).unwrap();
+
+        let part: GPTPartHeader<Opaque, u64> = GPTPartHeader::parse(&buf).unwrap();
+        assert_eq!(GUID::from(part.type_guid.0), GUID::LINUX_FS);
+    }
+
+    #[test]
+    fn type_guid_names() {
+        let root = DefaultGPTTypeGuid::LinuxRoot(Arch::Arm64);
+        assert_eq!(root.as_guid(), Some(Arch::Arm64.root()));
+        assert_eq!(root.name(), Some("Linux root (ARM64)"));
+        assert_eq!(root.os(), Some(OsFamily::Linux));
+        assert_eq!(root.to_string(), "Linux root (ARM64)");
+        assert!(!root.is_unused());
+
+        assert!(GUID::UNUSED.is_unused());
+        assert_eq!(GUID::ESP.display().to_string(), "EFI System");
+        assert_eq!(GUID::ESP.os(), Some(OsFamily::Uefi));
+
+        let guid: GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+        let unknown = DefaultGPTTypeGuid::Unknown(guid);
+        assert_eq!(unknown.name(), None);
+        assert_eq!(unknown.os(), None);
+        assert_eq!(unknown.to_string(), "6FCC8240-3985-4840-901F-A05E7FD9B69D");
+    }
 
     #[test]
     fn eq_guid_default_partition_type() {
@@ -0,0 +1,762 @@
+use crate::part::DefaultGPTTypeGuid;
+use crate::GUID;
+
+/// Operating system a partition type belongs to.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum OsFamily {
+    /// Types defined by `UEFI` or used independently of the operating system.
+    Uefi,
+    Linux,
+    Windows,
+    MacOS,
+    FreeBSD,
+    ChromeOS,
+    Android,
+}
+
+impl OsFamily {
+    /// Human-readable name of the operating system.
+    pub const fn name(self) -> &'static str {
+        match self {
+            OsFamily::Uefi => "UEFI",
+            OsFamily::Linux => "Linux",
+            OsFamily::Windows => "Windows",
+            OsFamily::MacOS => "macOS",
+            OsFamily::FreeBSD => "FreeBSD",
+            OsFamily::ChromeOS => "ChromeOS",
+            OsFamily::Android => "Android",
+        }
+    }
+}
+
+impl core::fmt::Display for OsFamily {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// CPU architecture of the Linux root and `/usr` partition types, as defined by the
+/// discoverable partitions specification.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Arm64,
+    Ia64,
+    LoongArch64,
+    RiscV32,
+    RiscV64,
+}
+
+impl Arch {
+    /// All known architectures.
+    pub const ALL: [Arch; 8] = [
+        Arch::X86,
+        Arch::X86_64,
+        Arch::Arm,
+        Arch::Arm64,
+        Arch::Ia64,
+        Arch::LoongArch64,
+        Arch::RiscV32,
+        Arch::RiscV64,
+    ];
+
+    /// Architecture this crate is compiled for, if it is known.
+    pub const fn native() -> Option<Arch> {
+        if cfg!(target_arch = "x86") {
+            Some(Arch::X86)
+        } else if cfg!(target_arch = "x86_64") {
+            Some(Arch::X86_64)
+        } else if cfg!(target_arch = "arm") {
+            Some(Arch::Arm)
+        } else if cfg!(target_arch = "aarch64") {
+            Some(Arch::Arm64)
+        } else if cfg!(target_arch = "loongarch64") {
+            Some(Arch::LoongArch64)
+        } else if cfg!(target_arch = "riscv32") {
+            Some(Arch::RiscV32)
+        } else if cfg!(target_arch = "riscv64") {
+            Some(Arch::RiscV64)
+        } else {
+            None
+        }
+    }
+
+    /// Human-readable name of the architecture.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Arch::X86 => "x86",
+            Arch::X86_64 => "x86-64",
+            Arch::Arm => "ARM",
+            Arch::Arm64 => "ARM64",
+            Arch::Ia64 => "IA-64",
+            Arch::LoongArch64 => "LoongArch64",
+            Arch::RiscV32 => "RISC-V 32",
+            Arch::RiscV64 => "RISC-V 64",
+        }
+    }
+
+    /// Type guid of the root partition for this architecture.
+    pub const fn root(self) -> GUID {
+        match self {
+            Arch::X86 => GUID::LINUX_ROOT_X86,
+            Arch::X86_64 => GUID::LINUX_ROOT_X86_64,
+            Arch::Arm => GUID::LINUX_ROOT_ARM,
+            Arch::Arm64 => GUID::LINUX_ROOT_ARM64,
+            Arch::Ia64 => GUID::LINUX_ROOT_IA64,
+            Arch::LoongArch64 => GUID::LINUX_ROOT_LOONGARCH64,
+            Arch::RiscV32 => GUID::LINUX_ROOT_RISCV32,
+            Arch::RiscV64 => GUID::LINUX_ROOT_RISCV64,
+        }
+    }
+
+    /// Type guid of the `/usr` partition for this architecture.
+    pub const fn usr(self) -> GUID {
+        match self {
+            Arch::X86 => GUID::LINUX_USR_X86,
+            Arch::X86_64 => GUID::LINUX_USR_X86_64,
+            Arch::Arm => GUID::LINUX_USR_ARM,
+            Arch::Arm64 => GUID::LINUX_USR_ARM64,
+            Arch::Ia64 => GUID::LINUX_USR_IA64,
+            Arch::LoongArch64 => GUID::LINUX_USR_LOONGARCH64,
+            Arch::RiscV32 => GUID::LINUX_USR_RISCV32,
+            Arch::RiscV64 => GUID::LINUX_USR_RISCV64,
+        }
+    }
+
+    /// Type guid of the dm-verity hash partition of the root partition for this architecture.
+    pub const fn root_verity(self) -> GUID {
+        match self {
+            Arch::X86 => GUID::LINUX_ROOT_VERITY_X86,
+            Arch::X86_64 => GUID::LINUX_ROOT_VERITY_X86_64,
+            Arch::Arm => GUID::LINUX_ROOT_VERITY_ARM,
+            Arch::Arm64 => GUID::LINUX_ROOT_VERITY_ARM64,
+            Arch::Ia64 => GUID::LINUX_ROOT_VERITY_IA64,
+            Arch::LoongArch64 => GUID::LINUX_ROOT_VERITY_LOONGARCH64,
+            Arch::RiscV32 => GUID::LINUX_ROOT_VERITY_RISCV32,
+            Arch::RiscV64 => GUID::LINUX_ROOT_VERITY_RISCV64,
+        }
+    }
+
+    /// Type guid of the dm-verity hash partition of the `/usr` partition for this architecture.
+    pub const fn usr_verity(self) -> GUID {
+        match self {
+            Arch::X86 => GUID::LINUX_USR_VERITY_X86,
+            Arch::X86_64 => GUID::LINUX_USR_VERITY_X86_64,
+            Arch::Arm => GUID::LINUX_USR_VERITY_ARM,
+            Arch::Arm64 => GUID::LINUX_USR_VERITY_ARM64,
+            Arch::Ia64 => GUID::LINUX_USR_VERITY_IA64,
+            Arch::LoongArch64 => GUID::LINUX_USR_VERITY_LOONGARCH64,
+            Arch::RiscV32 => GUID::LINUX_USR_VERITY_RISCV32,
+            Arch::RiscV64 => GUID::LINUX_USR_VERITY_RISCV64,
+        }
+    }
+}
+
+impl core::fmt::Display for Arch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Well known partition type, as listed in [`PartitionType::ALL`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct PartitionType {
+    /// Type guid stored in the partition entry.
+    pub guid: GUID,
+    /// Matching variant of [`DefaultGPTTypeGuid`].
+    pub kind: DefaultGPTTypeGuid,
+    /// Human-readable name of the partition type.
+    pub name: &'static str,
+    /// Operating system using the partition type.
+    pub os: OsFamily,
+}
+
+impl PartitionType {
+    /// All partition types known to this crate.
+    pub const ALL: &'static [PartitionType] = &PARTITION_TYPES;
+
+    const fn new(guid: GUID, kind: DefaultGPTTypeGuid, name: &'static str, os: OsFamily) -> Self {
+        Self {
+            guid,
+            kind,
+            name,
+            os,
+        }
+    }
+
+    /// Look up the partition type with the type guid `guid`.
+    pub fn from_guid(guid: GUID) -> Option<&'static Self> {
+        Self::ALL.iter().find(|t| t.guid == guid)
+    }
+
+    /// Look up the partition type by its name, ignoring ASCII case.
+    pub fn from_name(name: &str) -> Option<&'static Self> {
+        Self::ALL.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+}
+
+const PARTITION_TYPES: [PartitionType; 86] = [
+    PartitionType::new(
+        GUID::UNUSED,
+        DefaultGPTTypeGuid::Unused,
+        "Unused entry",
+        OsFamily::Uefi,
+    ),
+    PartitionType::new(
+        GUID::ESP,
+        DefaultGPTTypeGuid::ESP,
+        "EFI System",
+        OsFamily::Uefi,
+    ),
+    PartitionType::new(
+        GUID::LEGACY_MBR,
+        DefaultGPTTypeGuid::LegacyMBR,
+        "MBR partition scheme",
+        OsFamily::Uefi,
+    ),
+    PartitionType::new(
+        GUID::BIOS_BOOT,
+        DefaultGPTTypeGuid::BIOSBoot,
+        "BIOS boot",
+        OsFamily::Uefi,
+    ),
+    PartitionType::new(
+        GUID::XBOOTLDR,
+        DefaultGPTTypeGuid::XBootLdr,
+        "Linux extended boot",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_FS,
+        DefaultGPTTypeGuid::LinuxFilesystem,
+        "Linux filesystem",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_HOME,
+        DefaultGPTTypeGuid::LinuxHome,
+        "Linux home",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_SRV,
+        DefaultGPTTypeGuid::LinuxSrv,
+        "Linux server data",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_VAR,
+        DefaultGPTTypeGuid::LinuxVar,
+        "Linux variable data",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_VAR_TMP,
+        DefaultGPTTypeGuid::LinuxVarTmp,
+        "Linux temporary data",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_SWAP,
+        DefaultGPTTypeGuid::LinuxSwap,
+        "Linux swap",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_LVM,
+        DefaultGPTTypeGuid::LinuxLVM,
+        "Linux LVM",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_RAID,
+        DefaultGPTTypeGuid::LinuxRAID,
+        "Linux RAID",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_LUKS,
+        DefaultGPTTypeGuid::LinuxLUKS,
+        "Linux LUKS",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_DM_CRYPT,
+        DefaultGPTTypeGuid::LinuxDmCrypt,
+        "Linux plain dm-crypt",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_RESERVED,
+        DefaultGPTTypeGuid::LinuxReserved,
+        "Linux reserved",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::MICROSOFT_RESERVED,
+        DefaultGPTTypeGuid::MicrosoftReserved,
+        "Microsoft reserved",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::MICROSOFT_BASIC_DATA,
+        DefaultGPTTypeGuid::MicrosoftBasicData,
+        "Microsoft basic data",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::WINDOWS_RECOVERY,
+        DefaultGPTTypeGuid::WindowsRecovery,
+        "Windows recovery environment",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::WINDOWS_LDM_METADATA,
+        DefaultGPTTypeGuid::WindowsLDMMetadata,
+        "Windows LDM metadata",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::WINDOWS_LDM_DATA,
+        DefaultGPTTypeGuid::WindowsLDMData,
+        "Windows LDM data",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::WINDOWS_STORAGE_SPACES,
+        DefaultGPTTypeGuid::WindowsStorageSpaces,
+        "Windows Storage Spaces",
+        OsFamily::Windows,
+    ),
+    PartitionType::new(
+        GUID::APPLE_HFS_PLUS,
+        DefaultGPTTypeGuid::AppleHFSPlus,
+        "Apple HFS/HFS+",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::APPLE_APFS,
+        DefaultGPTTypeGuid::AppleAPFS,
+        "Apple APFS",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::APPLE_UFS,
+        DefaultGPTTypeGuid::AppleUFS,
+        "Apple UFS",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::APPLE_RAID,
+        DefaultGPTTypeGuid::AppleRAID,
+        "Apple RAID",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::APPLE_BOOT,
+        DefaultGPTTypeGuid::AppleBoot,
+        "Apple boot",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::APPLE_CORE_STORAGE,
+        DefaultGPTTypeGuid::AppleCoreStorage,
+        "Apple Core Storage",
+        OsFamily::MacOS,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_BOOT,
+        DefaultGPTTypeGuid::FreeBSDBoot,
+        "FreeBSD boot",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_DATA,
+        DefaultGPTTypeGuid::FreeBSDData,
+        "FreeBSD disklabel",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_SWAP,
+        DefaultGPTTypeGuid::FreeBSDSwap,
+        "FreeBSD swap",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_UFS,
+        DefaultGPTTypeGuid::FreeBSDUFS,
+        "FreeBSD UFS",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_VINUM,
+        DefaultGPTTypeGuid::FreeBSDVinum,
+        "FreeBSD Vinum",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::FREEBSD_ZFS,
+        DefaultGPTTypeGuid::FreeBSDZFS,
+        "FreeBSD ZFS",
+        OsFamily::FreeBSD,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_KERNEL,
+        DefaultGPTTypeGuid::ChromeOSKernel,
+        "ChromeOS kernel",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_ROOTFS,
+        DefaultGPTTypeGuid::ChromeOSRootfs,
+        "ChromeOS root filesystem",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_FIRMWARE,
+        DefaultGPTTypeGuid::ChromeOSFirmware,
+        "ChromeOS firmware",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_RESERVED,
+        DefaultGPTTypeGuid::ChromeOSReserved,
+        "ChromeOS reserved",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_MINIOS,
+        DefaultGPTTypeGuid::ChromeOSMiniOS,
+        "ChromeOS miniOS",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::CHROMEOS_HIBERNATE,
+        DefaultGPTTypeGuid::ChromeOSHibernate,
+        "ChromeOS hibernate",
+        OsFamily::ChromeOS,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_BOOTLOADER,
+        DefaultGPTTypeGuid::AndroidBootloader,
+        "Android bootloader",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_BOOTLOADER2,
+        DefaultGPTTypeGuid::AndroidBootloader2,
+        "Android bootloader 2",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_BOOT,
+        DefaultGPTTypeGuid::AndroidBoot,
+        "Android boot",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_RECOVERY,
+        DefaultGPTTypeGuid::AndroidRecovery,
+        "Android recovery",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_MISC,
+        DefaultGPTTypeGuid::AndroidMisc,
+        "Android misc",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_METADATA,
+        DefaultGPTTypeGuid::AndroidMetadata,
+        "Android metadata",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_SYSTEM,
+        DefaultGPTTypeGuid::AndroidSystem,
+        "Android system",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_CACHE,
+        DefaultGPTTypeGuid::AndroidCache,
+        "Android cache",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_DATA,
+        DefaultGPTTypeGuid::AndroidData,
+        "Android data",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_PERSISTENT,
+        DefaultGPTTypeGuid::AndroidPersistent,
+        "Android persistent",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_VENDOR,
+        DefaultGPTTypeGuid::AndroidVendor,
+        "Android vendor",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_CONFIG,
+        DefaultGPTTypeGuid::AndroidConfig,
+        "Android config",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_FACTORY,
+        DefaultGPTTypeGuid::AndroidFactory,
+        "Android factory",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::ANDROID_OEM,
+        DefaultGPTTypeGuid::AndroidOEM,
+        "Android OEM",
+        OsFamily::Android,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_X86,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::X86),
+        "Linux root (x86)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_X86_64,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::X86_64),
+        "Linux root (x86-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_ARM,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::Arm),
+        "Linux root (ARM)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_ARM64,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::Arm64),
+        "Linux root (ARM64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_IA64,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::Ia64),
+        "Linux root (IA-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_LOONGARCH64,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::LoongArch64),
+        "Linux root (LoongArch64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_RISCV32,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::RiscV32),
+        "Linux root (RISC-V 32)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_RISCV64,
+        DefaultGPTTypeGuid::LinuxRoot(Arch::RiscV64),
+        "Linux root (RISC-V 64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_X86,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::X86),
+        "Linux /usr (x86)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_X86_64,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::X86_64),
+        "Linux /usr (x86-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_ARM,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::Arm),
+        "Linux /usr (ARM)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_ARM64,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::Arm64),
+        "Linux /usr (ARM64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_IA64,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::Ia64),
+        "Linux /usr (IA-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_LOONGARCH64,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::LoongArch64),
+        "Linux /usr (LoongArch64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_RISCV32,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::RiscV32),
+        "Linux /usr (RISC-V 32)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_RISCV64,
+        DefaultGPTTypeGuid::LinuxUsr(Arch::RiscV64),
+        "Linux /usr (RISC-V 64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_X86,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::X86),
+        "Linux root verity (x86)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_X86_64,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::X86_64),
+        "Linux root verity (x86-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_ARM,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::Arm),
+        "Linux root verity (ARM)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_ARM64,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::Arm64),
+        "Linux root verity (ARM64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_IA64,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::Ia64),
+        "Linux root verity (IA-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_LOONGARCH64,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::LoongArch64),
+        "Linux root verity (LoongArch64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_RISCV32,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::RiscV32),
+        "Linux root verity (RISC-V 32)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_ROOT_VERITY_RISCV64,
+        DefaultGPTTypeGuid::LinuxRootVerity(Arch::RiscV64),
+        "Linux root verity (RISC-V 64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_X86,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::X86),
+        "Linux /usr verity (x86)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_X86_64,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::X86_64),
+        "Linux /usr verity (x86-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_ARM,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Arm),
+        "Linux /usr verity (ARM)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_ARM64,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Arm64),
+        "Linux /usr verity (ARM64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_IA64,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::Ia64),
+        "Linux /usr verity (IA-64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_LOONGARCH64,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::LoongArch64),
+        "Linux /usr verity (LoongArch64)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_RISCV32,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::RiscV32),
+        "Linux /usr verity (RISC-V 32)",
+        OsFamily::Linux,
+    ),
+    PartitionType::new(
+        GUID::LINUX_USR_VERITY_RISCV64,
+        DefaultGPTTypeGuid::LinuxUsrVerity(Arch::RiscV64),
+        "Linux /usr verity (RISC-V 64)",
+        OsFamily::Linux,
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::{Arch, OsFamily, PartitionType};
+    use crate::part::DefaultGPTTypeGuid;
+    use crate::GUID;
+
+    #[test]
+    fn unique() {
+        for (i, lhs) in PartitionType::ALL.iter().enumerate() {
+            for rhs in &PartitionType::ALL[i + 1..] {
+                assert_ne!(lhs.guid, rhs.guid, "{} and {}", lhs.name, rhs.name);
+                assert_ne!(lhs.kind, rhs.kind, "{} and {}", lhs.name, rhs.name);
+                assert!(!lhs.name.eq_ignore_ascii_case(rhs.name), "{}", lhs.name);
+            }
+        }
+    }
+
+    #[test]
+    fn kind_roundtrip() {
+        for t in PartitionType::ALL {
+            assert_eq!(DefaultGPTTypeGuid::from(t.guid), t.kind, "{}", t.name);
+            assert_eq!(GUID::from(t.kind), t.guid, "{}", t.name);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let linux: GUID = "0FC63DAF-8483-4772-8E79-3D69D8477DE4".parse().unwrap();
+        let t = PartitionType::from_guid(linux).unwrap();
+        assert_eq!(t.kind, DefaultGPTTypeGuid::LinuxFilesystem);
+        assert_eq!(t.os, OsFamily::Linux);
+
+        let t = PartitionType::from_name("linux root (x86-64)").unwrap();
+        assert_eq!(t.kind, DefaultGPTTypeGuid::LinuxRoot(Arch::X86_64));
+        assert_eq!(
+            t.guid,
+            "4F68BCE3-E8CD-4DB1-96E7-FBCAF984B709".parse().unwrap()
+        );
+
+        assert!(PartitionType::from_name("Linux root").is_none());
+        let random: GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+        assert!(PartitionType::from_guid(random).is_none());
+    }
+}
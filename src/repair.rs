@@ -0,0 +1,59 @@
+use block_device::BlockDevice;
+
+use crate::header::GptHeaderType;
+use crate::{ceil64, read_buf, write_header, write_table_blocks, GPTError, Result, GPT};
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Regenerate the `broken` header and its partition entry array from the valid copy.
+    ///
+    /// The partition entry array is copied from the valid header, and the broken header is
+    /// rebuilt with its [`crate::header::GPTHeader::my_lba`], `other_lba` and `p_entry_lba`
+    /// pointing to the usual places: A primary partition entry array directly follows the
+    /// primary header at LBA 1, a backup partition entry array directly precedes the backup
+    /// header. Both crcs are recalculated before writing.
+    ///
+    /// `self` has to be the [`GPT`] returned with [`crate::GPTParseError::BrokenHeader`], and
+    /// `broken` the header type returned with it. Afterwards the primary header is used, like
+    /// after opening a valid partition table.
+    pub fn repair(&mut self, broken: GptHeaderType) -> Result<()> {
+        let is_primary = self.header.my_lba < self.header.other_lba;
+        match (broken, is_primary) {
+            (GptHeaderType::Main, false) | (GptHeaderType::Backup, true) => {}
+            _ => return Err(GPTError::InvalidData),
+        }
+
+        let p_table_size = self.header.size_of_p_entry as usize * self.header.num_parts as usize;
+        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64);
+
+        let mut buf = read_buf(
+            self.header.p_entry_lba as usize,
+            p_table_size,
+            &self.block,
+            blocks as usize,
+        )?;
+        self.header.validate_part_crc(&buf)?;
+
+        let p_entry_lba = self.header.alternate_p_entry_lba(T::BLOCK_SIZE)?;
+        let header = self.header.alternate(p_entry_lba);
+
+        // `buf` holds the complete blocks of the partition entry array.
+        let (table, scratch) = buf.split_at_mut(blocks as usize * T::BLOCK_SIZE as usize);
+        write_table_blocks(&self.block, header.p_entry_lba, table, scratch)?;
+
+        // The partition entries are written, the buffer can be reused.
+        write_header(&self.block, &header, &mut buf)?;
+
+        if is_primary {
+            self.alt_p_entry_lba = p_entry_lba;
+        } else {
+            self.alt_p_entry_lba = self.header.p_entry_lba;
+            self.header = header;
+        }
+
+        Ok(())
+    }
+}
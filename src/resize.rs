@@ -0,0 +1,185 @@
+use block_device::BlockDevice;
+
+use crate::mbr::{MBRPartitionRecord, MasterBootRecord, MbrType};
+use crate::part::RawPartHeader;
+use crate::{
+    ceil64, read_buf, write_header, write_table_blocks, BlockDeviceSize, GPTError, Result, GPT,
+    GUID,
+};
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Move the backup partition entry array and header to the end of the device, after the
+    /// device was resized (like `sgdisk -e`).
+    ///
+    /// Updates [`crate::header::GPTHeader::last_lba`] and `other_lba` of both headers, and the
+    /// size of the protective MBR partition. The protective partition of a hybrid MBR is kept,
+    /// as it only covers the GPT structures. The new backup is written before the primary header
+    /// is pointed to it, so an interrupted relocation leaves a valid primary copy.
+    pub fn relocate_backup_to_end(&mut self) -> Result<()>
+    where
+        T: BlockDeviceSize,
+    {
+        let num_blocks = self.block.num_blocks()?;
+
+        let mut primary = if self.header.my_lba < self.header.other_lba {
+            self.header.clone()
+        } else {
+            self.header.alternate(self.alt_p_entry_lba)
+        };
+
+        let p_table_size = primary.size_of_p_entry as usize * primary.num_parts as usize;
+        let blocks = ceil64(p_table_size as u64, T::BLOCK_SIZE as u64);
+
+        let backup_lba = num_blocks
+            .checked_sub(1)
+            .ok_or(GPTError::InvalidLba(num_blocks))?;
+        let backup_p_entry_lba = backup_lba
+            .checked_sub(blocks)
+            .filter(|&lba| lba > primary.first_lba)
+            .ok_or(GPTError::InvalidLba(backup_lba))?;
+        let last_lba = backup_p_entry_lba - 1;
+
+        let mut buf = read_buf(
+            primary.p_entry_lba as usize,
+            p_table_size,
+            &self.block,
+            blocks as usize,
+        )?;
+        primary.validate_part_crc(&buf)?;
+
+        // Refuse to cut off partitions, if the device got smaller.
+        for idx in 0..primary.num_parts {
+            let offset = idx as usize * primary.size_of_p_entry as usize;
+            let part = RawPartHeader::parse(&buf[offset..])?;
+            if part.type_guid != GUID::UNUSED && part.end_lba > last_lba {
+                return Err(GPTError::InvalidLba(part.end_lba));
+            }
+        }
+
+        // The protective partition is resized to the end of the device, so it has to start on it.
+        let protective_past_end = self.mbr_partition.iter().any(|part| {
+            part.os_indicator == MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE
+                && part.starting_lba() as u64 >= num_blocks
+        });
+        if self.mbr_type == MbrType::Protective && protective_past_end {
+            return Err(GPTError::InvalidMbr);
+        }
+
+        // Both copies end up with the primary partition entry array. The cache is dropped, if
+        // writing fails.
+        #[cfg(feature = "alloc")]
+        let cache = self.cache.take().map(|mut cache| {
+            cache.clear();
+            cache.extend_from_slice(&buf[..p_table_size]);
+            cache
+        });
+
+        primary.other_lba = backup_lba;
+        primary.last_lba = last_lba;
+        primary.crc32 = primary.calculate_crc();
+        let backup = primary.alternate(backup_p_entry_lba);
+
+        // `buf` holds the complete blocks of the partition entry array.
+        let (table, scratch) = buf.split_at_mut(blocks as usize * T::BLOCK_SIZE as usize);
+        write_table_blocks(&self.block, backup.p_entry_lba, table, scratch)?;
+
+        // The partition entries are written, the buffer can be reused.
+        write_header(&self.block, &backup, &mut buf)?;
+        write_header(&self.block, &primary, &mut buf)?;
+
+        let block_size = T::BLOCK_SIZE as usize;
+        self.block.read(&mut buf[..block_size], 0, 1)?;
+        let mut mbr = MasterBootRecord::parse(&buf)?;
+        if self.mbr_type == MbrType::Protective {
+            for part in mbr
+                .partition
+                .iter_mut()
+                .filter(|part| part.os_indicator == MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE)
+            {
+                let size_in_lba = num_blocks
+                    .checked_sub(part.starting_lba() as u64)
+                    .ok_or(GPTError::InvalidMbr)?;
+                let size_in_lba = core::cmp::min(size_in_lba, u32::MAX as u64) as u32;
+                part.set_protective_lbas(part.starting_lba(), size_in_lba);
+            }
+            mbr.write_to(&mut buf)?;
+            self.block.write(&buf[..block_size], 0, 1)?;
+        }
+        self.mbr_partition = mbr.partition;
+
+        self.header = primary;
+        self.alt_p_entry_lba = backup_p_entry_lba;
+        #[cfg(feature = "alloc")]
+        {
+            self.cache = cache;
+        }
+
+        Ok(())
+    }
+
+    /// Grow the partition at `idx` into the free space following it, up to the next partition or
+    /// [`crate::header::GPTHeader::last_lba`] (like `growpart`). Returns the new end of the
+    /// partition.
+    ///
+    /// The new end is chosen so the partition ends right before a multiple of `alignment`
+    /// blocks. Fails with [`GPTError::NoFreeSpace`] if the partition cannot grow. The change is
+    /// written right away, so it fails with [`GPTError::StagedChanges`] if other changes are
+    /// staged but not yet written by [`Self::commit`]. On errors, nothing is left staged.
+    #[cfg(any(feature = "alloc", doc))]
+    pub fn grow_partition_to_max(&mut self, idx: u32, alignment: u64) -> Result<u64> {
+        if self.has_staged_changes() {
+            return Err(GPTError::StagedChanges);
+        }
+
+        let grown = self
+            .max_partition_range(idx, alignment)
+            .and_then(|(start_lba, end_lba)| {
+                self.resize_partition(idx, start_lba, end_lba)?;
+                self.commit()?;
+                Ok(end_lba)
+            });
+        if grown.is_err() {
+            self.discard();
+        }
+
+        grown
+    }
+
+    /// Start and largest aligned end of the partition at `idx` in the staged table.
+    #[cfg(any(feature = "alloc", doc))]
+    fn max_partition_range(&mut self, idx: u32, alignment: u64) -> Result<(u64, u64)> {
+        let alignment = core::cmp::max(alignment, 1);
+
+        let num_parts = self.header.num_parts;
+        let size = self.header.size_of_p_entry as usize;
+        let mut limit = self.header.last_lba;
+
+        let table = self.staged_table()?;
+        let parse = |idx: u32| RawPartHeader::parse(&table[idx as usize * size..]);
+        if idx >= num_parts {
+            return Err(GPTError::InvalidData);
+        }
+        let part = parse(idx)?;
+        if part.type_guid == GUID::UNUSED {
+            return Err(GPTError::InvalidData);
+        }
+
+        for other in 0..num_parts {
+            let other = parse(other)?;
+            if other.type_guid != GUID::UNUSED && other.start_lba > part.end_lba {
+                limit = core::cmp::min(limit, other.start_lba - 1);
+            }
+        }
+
+        let end_lba = (limit + 1) / alignment * alignment;
+        if end_lba <= part.end_lba + 1 {
+            return Err(GPTError::NoFreeSpace(idx));
+        }
+
+        Ok((part.start_lba, end_lba - 1))
+    }
+}
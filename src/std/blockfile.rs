@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::Error;
+use std::io::{Error, Seek, SeekFrom};
 use std::path::Path;
 
 #[cfg(target_family = "windows")]
@@ -9,6 +9,9 @@ use std::os::windows::fs::FileExt;
 use std::os::unix::fs::FileExt;
 
 use block_device::BlockDevice;
+
+use crate::BlockDeviceSize;
+
 pub struct BlockFile<const N: u32> {
     inner: std::fs::File,
 }
@@ -72,6 +75,19 @@ impl<const N: u32> BlockDevice for BlockFile<N> {
     }
 }
 
+impl<const N: u32> BlockDeviceSize for BlockFile<N> {
+    fn num_blocks(&self) -> Result<u64, Self::Error> {
+        let mut len = self.inner.metadata()?.len();
+
+        // Block devices report a length of 0, but can seek to their end.
+        if len == 0 {
+            len = (&self.inner).seek(SeekFrom::End(0))?;
+        }
+
+        Ok(len / N as u64)
+    }
+}
+
 impl<const N: u32> From<File> for BlockFile<N> {
     fn from(f: File) -> Self {
         Self { inner: f }
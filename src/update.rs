@@ -0,0 +1,99 @@
+use block_device::BlockDevice;
+use crc::{crc32, Hasher32};
+
+use crate::part::{GPTPartHeader, GPTTypeGuid, RawPartHeader};
+use crate::{ceil64, write_header, GPTError, Result, GPT};
+
+impl<T> GPT<T>
+where
+    T: BlockDevice,
+    GPTError: From<T::Error>,
+{
+    /// Write the partition entry `part` at `idx` directly to both copies of the partition table,
+    /// without allocations. `buf` is used as scratch space of at least one block.
+    ///
+    /// The crc of the partition entry array is recomputed by reading it block by block. Like
+    /// [`Self::commit`], the backup copy is written before the primary one. Only the block
+    /// holding the entry is written, so both copies of the partition entry array are expected to
+    /// be equal.
+    pub fn write_partition_with_buf<PT, PA>(
+        &mut self,
+        idx: u32,
+        part: &GPTPartHeader<PT, PA>,
+        buf: &mut [u8],
+    ) -> Result<()>
+    where
+        PT: GPTTypeGuid + Clone,
+        GPTError: From<<PT as TryInto<[u8; 16]>>::Error>,
+        PA: TryInto<u64> + Clone,
+        GPTError: From<<PA as TryInto<u64>>::Error>,
+    {
+        if idx >= self.header.num_parts {
+            return Err(GPTError::InvalidData);
+        }
+
+        let block_size = T::BLOCK_SIZE as usize;
+        let buf = buf.get_mut(..block_size).ok_or(GPTError::UnexpectedEOF)?;
+
+        let mut entry = [0u8; RawPartHeader::SIZE];
+        part.write_to(&mut entry)?;
+
+        let offset = idx as usize * self.header.size_of_p_entry as usize;
+        let entry_block = (offset / block_size) as u64;
+        let in_block = offset % block_size;
+        if in_block + entry.len() > block_size {
+            return Err(GPTError::UnexpectedEOF);
+        }
+
+        // Read the entry block of the primary copy and replace the entry.
+        let read_entry_block = |buf: &mut [u8]| -> Result<()> {
+            self.block
+                .read(buf, (self.header.p_entry_lba + entry_block) as usize, 1)?;
+            buf[in_block..in_block + entry.len()].copy_from_slice(&entry);
+            Ok(())
+        };
+
+        let p_table_size = self.header.num_parts as usize * self.header.size_of_p_entry as usize;
+        let blocks = ceil64(p_table_size as u64, block_size as u64);
+        let mut digest = crc32::Digest::new(crc32::IEEE);
+        for block in 0..blocks {
+            if block == entry_block {
+                read_entry_block(buf)?;
+            } else {
+                self.block
+                    .read(buf, (self.header.p_entry_lba + block) as usize, 1)?;
+            }
+            let len = core::cmp::min(block_size, p_table_size - block as usize * block_size);
+            digest.write(&buf[..len]);
+        }
+
+        let mut header = self.header.clone();
+        header.p_crc32 = digest.sum32();
+        header.crc32 = header.calculate_crc();
+        let alt_header = header.alternate(self.alt_p_entry_lba);
+
+        let (primary, backup) = if header.my_lba < alt_header.my_lba {
+            (&header, &alt_header)
+        } else {
+            (&alt_header, &header)
+        };
+        for header in [backup, primary] {
+            read_entry_block(buf)?;
+            self.block
+                .write(buf, (header.p_entry_lba + entry_block) as usize, 1)?;
+            write_header(&self.block, header, buf)?;
+        }
+
+        #[cfg(feature = "alloc")]
+        for table in [self.staged.as_mut(), self.cache.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            table[offset..offset + entry.len()].copy_from_slice(&entry);
+        }
+
+        self.header = header;
+
+        Ok(())
+    }
+}
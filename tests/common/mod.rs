@@ -0,0 +1,11 @@
+//! Helpers shared by the integration tests.
+
+use std::path::PathBuf;
+
+/// Path of the fixture image with 512 byte blocks, independent of the working directory.
+pub fn fixture_512_path() -> PathBuf {
+    PathBuf::from(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/gpt-linux-disk-01.img"
+    ))
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "derive")]
+
+use nogpt::part::{Arch, GPTTypeGuid, GptTypeGuid, OsFamily};
+use nogpt::{GPTError, GUID};
+
+#[cfg(feature = "std")]
+mod common;
+
+#[derive(Debug, Clone, PartialEq, Eq, GptTypeGuid)]
+enum VendorType {
+    #[guid("00000000-0000-0000-0000-000000000000", name = "Unused entry")]
+    Unused,
+    #[guid("0FC63DAF-8483-4772-8E79-3D69D8477DE4", name = "Vendor data")]
+    Data,
+    #[guid("6FCC8240-3985-4840-901F-A05E7FD9B69D", os = Uefi, arch = Arm64)]
+    Firmware,
+    #[unknown]
+    Other(GUID),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GptTypeGuid)]
+enum StrictType {
+    #[guid("6FCC8240-3985-4840-901F-A05E7FD9B69D")]
+    Firmware,
+}
+
+#[test]
+fn conversions() {
+    let firmware: GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+
+    assert_eq!(VendorType::from(firmware.as_bytes()), VendorType::Firmware);
+    assert_eq!(<[u8; 16]>::from(VendorType::Firmware), firmware.as_bytes());
+    assert_eq!(VendorType::from([0u8; 16]), VendorType::Unused);
+    assert_eq!(VendorType::Firmware.as_guid(), Some(firmware));
+
+    assert_eq!(
+        VendorType::from(GUID::ESP.as_bytes()),
+        VendorType::Other(GUID::ESP)
+    );
+    assert_eq!(
+        <[u8; 16]>::from(VendorType::Other(GUID::ESP)),
+        GUID::ESP.as_bytes()
+    );
+
+    assert_eq!(
+        StrictType::try_from(firmware.as_bytes()).unwrap(),
+        StrictType::Firmware
+    );
+    assert!(matches!(
+        StrictType::try_from(GUID::ESP.as_bytes()),
+        Err(GPTError::InvalidData)
+    ));
+}
+
+#[test]
+fn names() {
+    assert_eq!(VendorType::Data.name(), Some("Vendor data"));
+    assert_eq!(VendorType::Firmware.name(), Some("Firmware"));
+    assert_eq!(VendorType::Other(GUID::ESP).name(), Some("EFI System"));
+    assert!(VendorType::Unused.is_unused());
+
+    assert_eq!(VendorType::Data.to_string(), "Vendor data");
+    assert_eq!(VendorType::Other(GUID::ESP).to_string(), "EFI System");
+}
+
+#[test]
+fn metadata() {
+    // The type guid of Linux filesystems doesn't make the vendor type a Linux type.
+    assert_eq!(VendorType::Data.os(), None);
+    assert_eq!(VendorType::Data.arch(), None);
+    assert_eq!(VendorType::Firmware.os(), Some(OsFamily::Uefi));
+    assert_eq!(VendorType::Firmware.arch(), Some(Arch::Arm64));
+
+    let root = VendorType::Other(GUID::LINUX_ROOT_X86_64);
+    assert_eq!(root.os(), Some(OsFamily::Linux));
+    assert_eq!(root.arch(), Some(Arch::X86_64));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn file() -> Result<(), GPTError> {
+    use nogpt::GptRepair;
+
+    let block = nogpt::std::BlockFile::<512>::open(&common::fixture_512_path())?;
+    let gpt = nogpt::GPT::open(block).fail()?;
+
+    let part: nogpt::part::GPTPartHeader<VendorType> =
+        gpt.get_first_partition_of_type(VendorType::Data)?;
+    assert_eq!((part.start_lba, part.end_lba), (34, 62));
+
+    let parts = gpt
+        .used_partitions::<VendorType, nogpt::part::Attributes>()?
+        .map(|part| part.map(|(_, part)| part.type_guid.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(parts, ["Vendor data"]);
+
+    Ok(())
+}
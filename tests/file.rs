@@ -1,15 +1,22 @@
 #[cfg(feature = "std")]
 use block_device::BlockDevice;
 #[cfg(feature = "std")]
-use nogpt::mbr::MasterBootRecord;
+use nogpt::header::{GPTHeader, GptHeaderType};
 #[cfg(feature = "std")]
-use nogpt::part::{DefaultGPTTypeGuid, GPTPartHeader};
+use nogpt::mbr::{Chs, Geometry, MBRPartition, MBRPartitionRecord, MasterBootRecord, MbrType};
 #[cfg(feature = "std")]
-use nogpt::{GPTError, GptRepair};
+use nogpt::part::{Attributes, DefaultGPTTypeGuid, GPTPartHeader};
+#[cfg(feature = "std")]
+use nogpt::{BlockDeviceSize, GPTError, GPTParseError, GptRepair, HybridPartition};
 
 #[cfg(feature = "std")]
 use nogpt::std::BlockFile;
 
+#[cfg(feature = "std")]
+mod common;
+#[cfg(feature = "std")]
+use common::fixture_512_path;
+
 #[cfg(feature = "std")]
 #[test]
 fn file() -> Result<(), GPTError> {
@@ -34,10 +41,205 @@ fn find_linux() -> Result<(), GPTError> {
     let type_guid: nogpt::GUID = "0FC63DAF-8483-4772-8E79-3D69D8477DE4".parse().unwrap();
     let guid: nogpt::GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
     let part: GPTPartHeader =
-        gpt.get_first_partition_of_type(DefaultGPTTypeGuid::Unknown(type_guid))?;
+        gpt.get_first_partition_of_type(DefaultGPTTypeGuid::LinuxFilesystem)?;
+
+    assert_eq!(part.type_guid, DefaultGPTTypeGuid::LinuxFilesystem);
+    assert_eq!(nogpt::GUID::from(part.type_guid), type_guid);
+    assert_eq!(part.guid, guid);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn partitions() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+
+    let gpt = nogpt::GPT::open(block).fail()?;
+
+    let parts: Vec<(u32, GPTPartHeader)> = gpt.partitions()?.collect::<Result<_, _>>()?;
+    assert_eq!(parts.len(), 128);
+    assert_eq!(parts[1].1.type_guid, DefaultGPTTypeGuid::Unused);
+
+    let parts: Vec<(u32, GPTPartHeader)> = gpt.used_partitions()?.collect::<Result<_, _>>()?;
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].0, 0);
+    assert_eq!(parts[0].1.name_str, "primary");
+
+    let mut buf = [0u8; 512];
+    let mut parts = gpt.used_partitions_with_buf::<DefaultGPTTypeGuid, Attributes>(&mut buf);
+    let (idx, part) = parts.next().unwrap()?;
+    assert_eq!((idx, part.start_lba, part.end_lba), (0, 34, 62));
+    assert!(parts.next().is_none());
+
+    // The buffer is only used if the partition entry array is not cached.
+    let mut buf = [0u8; 256];
+    let mut parts = gpt.partitions_with_buf::<DefaultGPTTypeGuid, Attributes>(&mut buf);
+    assert!(matches!(parts.next(), Some(Ok((0, _)))));
+
+    let mut block_buf = [0u8; 512];
+    let gpt = nogpt::GPT::open_with_buf(open_512_file()?, &mut block_buf).fail()?;
+    let mut parts = gpt.partitions_with_buf::<DefaultGPTTypeGuid, Attributes>(&mut buf);
+    assert!(matches!(parts.next(), Some(Err(GPTError::UnexpectedEOF))));
+    assert!(parts.next().is_none());
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn find_by_guid_name() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+
+    let gpt = nogpt::GPT::open(block).fail()?;
+
+    let guid: nogpt::GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+    let (idx, part): (u32, GPTPartHeader) = gpt.find_by_guid(&guid)?.unwrap();
+    assert_eq!((idx, part.start_lba), (0, 34));
+    assert!(gpt
+        .find_by_guid::<DefaultGPTTypeGuid, Attributes>(&nogpt::GUID::ESP)?
+        .is_none());
+
+    let (idx, part): (u32, GPTPartHeader) = gpt.find_by_name("primary")?.unwrap();
+    assert_eq!((idx, part.guid), (0, guid));
+    assert!(gpt
+        .find_by_name::<DefaultGPTTypeGuid, Attributes>("prim")?
+        .is_none());
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn open_with_buf() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+
+    // The partition entry array spans 32 blocks, but is streamed through a single one.
+    let mut buf = [0u8; 512];
+    let gpt = nogpt::GPT::open_with_buf(block, &mut buf).fail()?;
+
+    let guid: nogpt::GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+    let part: GPTPartHeader =
+        gpt.get_first_partition_of_type_with_buf(DefaultGPTTypeGuid::LinuxFilesystem, &mut buf)?;
+    assert_eq!(part.guid, guid);
+
+    let (idx, _): (u32, GPTPartHeader) = gpt.find_by_guid_with_buf(&guid, &mut buf)?.unwrap();
+    assert_eq!(idx, 0);
+    let (idx, _): (u32, GPTPartHeader) = gpt.find_by_name_with_buf("primary", &mut buf)?.unwrap();
+    assert_eq!(idx, 0);
+    assert!(gpt
+        .find_by_name_with_buf::<DefaultGPTTypeGuid, Attributes>("prim", &mut buf)?
+        .is_none());
+
+    let mut small = [0u8; 256];
+    assert!(matches!(
+        nogpt::GPT::open_with_buf(open_512_file()?, &mut small).fail(),
+        Err(GPTError::UnexpectedEOF)
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cache_reload() -> Result<(), GPTError> {
+    let block = copy_512_file("cache_reload")?;
+    let mut gpt = nogpt::GPT::open(block).fail()?;
+
+    // Change the partition table behind the back of the cached GPT.
+    let path = tmp_512_path("cache_reload");
+    let mut buf = [0u8; 512];
+    let mut other = nogpt::GPT::open_with_buf(BlockFile::<512>::open(&path)?, &mut buf).fail()?;
+    let mut part: GPTPartHeader = other.get_partition(0)?;
+    part.set_name("renamed")?;
+    other.write_partition_with_buf(0, &part, &mut buf)?;
+
+    let part: GPTPartHeader = other.get_partition(0)?;
+    assert!(part.name_eq("renamed"));
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert!(part.name_eq("primary"));
+    // The lookups with a buffer use the cache as well.
+    assert!(gpt
+        .find_by_name_with_buf::<DefaultGPTTypeGuid, Attributes>("renamed", &mut buf)?
+        .is_none());
+    let part: GPTPartHeader = gpt.get_partition_with_buf(0, &mut buf)?;
+    assert!(part.name_eq("primary"));
+
+    // Staged changes were made against the old partition table.
+    gpt.set_name(0, "staged")?;
+    assert!(matches!(gpt.reload(), Err(GPTError::StagedChanges)));
+    gpt.discard();
+
+    gpt.reload()?;
+    let (idx, _): (u32, GPTPartHeader) = gpt.find_by_name("renamed")?.unwrap();
+    assert_eq!(idx, 0);
+
+    // Writes through the GPT update the cache.
+    gpt.set_name(0, "again")?;
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert!(part.name_eq("renamed"));
+    gpt.commit()?;
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert!(part.name_eq("again"));
+
+    let mut part = part;
+    part.set_name("direct")?;
+    gpt.write_partition_with_buf(0, &part, &mut buf)?;
+    let parts: Vec<(u32, GPTPartHeader)> = gpt.used_partitions()?.collect::<Result<_, _>>()?;
+    assert!(parts[0].1.name_eq("direct"));
+
+    // Reloading picks up a moved backup partition entry array and a new MBR.
+    resize_512_file("cache_reload", 128)?;
+    let mut other = nogpt::GPT::open_with_buf(BlockFile::<512>::open(&path)?, &mut buf).fail()?;
+    other.relocate_backup_to_end()?;
+    other.create_hybrid_mbr(&[HybridPartition::new(0, 0x83)], &mut buf)?;
+
+    gpt.reload()?;
+    assert_eq!(gpt.mbr_type(), MbrType::Hybrid);
+    gpt.set_name(0, "moved")?;
+    gpt.commit()?;
+
+    let gpt = nogpt::GPT::open_sized(gpt.get_block()).fail()?;
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert!(part.name_eq("moved"));
+    let block = gpt.get_block();
+    block.read(&mut buf, 127, 1)?;
+    assert_eq!(GPTHeader::parse(&buf)?.p_entry_lba, 95);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn get_partition_verified() -> Result<(), GPTError> {
+    let block = copy_512_file("get_partition_verified")?;
+
+    let mut buf = [0u8; 512];
+    let gpt = nogpt::GPT::open_with_buf(block, &mut buf).fail()?;
+
+    let guid: nogpt::GUID = "6FCC8240-3985-4840-901F-A05E7FD9B69D".parse().unwrap();
+    let part: GPTPartHeader = gpt.get_partition_verified_with_buf(0, &mut buf)?;
+    assert_eq!(part.guid, guid);
+    let part: GPTPartHeader = gpt.get_partition_verified_with_buf(127, &mut buf)?;
+    assert_eq!(part.type_guid, DefaultGPTTypeGuid::Unused);
+    assert!(gpt
+        .get_partition_verified_with_buf::<DefaultGPTTypeGuid, Attributes>(128, &mut buf)
+        .is_err());
+
+    // Corrupt an unused entry in the last block of the partition entry array.
+    let path = tmp_512_path("get_partition_verified");
+    let other = BlockFile::<512>::open(&path)?;
+    other.read(&mut buf, 33, 1)?;
+    buf[100] = 0xff;
+    other.write(&buf, 33, 1)?;
 
-    assert_eq!(part.type_guid, DefaultGPTTypeGuid::Unknown(type_guid));
+    // Only the block holding the entry is read, so the damage goes unnoticed.
+    let part: GPTPartHeader = gpt.get_partition(0)?;
     assert_eq!(part.guid, guid);
+    assert!(matches!(
+        gpt.get_partition_verified_with_buf::<DefaultGPTTypeGuid, Attributes>(0, &mut buf),
+        Err(GPTError::InvalidCrcParts(_, _))
+    ));
 
     Ok(())
 }
@@ -51,7 +253,7 @@ fn read_mbr() -> Result<(), GPTError> {
 
     block.read(&mut buf, 0, 1)?;
 
-    let mbr = unsafe { MasterBootRecord::from_buf(&buf) }?;
+    let mbr = MasterBootRecord::parse(&buf)?;
 
     assert_eq!(mbr.signature(), 0xaa55);
 
@@ -68,9 +270,807 @@ fn read_mbr() -> Result<(), GPTError> {
     Ok(())
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn legacy_mbr() -> Result<(), GPTError> {
+    let block = blank_512_file("legacy_mbr", 200)?;
+
+    let record = |os_indicator: u8, start: u32, size: u32| MBRPartitionRecord {
+        os_indicator,
+        starting_lba: start.to_le_bytes(),
+        size_in_lba: size.to_le_bytes(),
+        ..MBRPartitionRecord::EMPTY
+    };
+    let write_mbr = |lba: usize, partition: [MBRPartitionRecord; 2]| -> Result<(), GPTError> {
+        let mut mbr = MasterBootRecord::new_protective(200);
+        mbr.partition = [
+            partition[0],
+            partition[1],
+            MBRPartitionRecord::EMPTY,
+            MBRPartitionRecord::EMPTY,
+        ];
+        let mut buf = [0u8; 512];
+        mbr.write_to(&mut buf)?;
+        block.write(&buf, lba, 1)?;
+        Ok(())
+    };
+
+    // Two primary partitions, the second one extended with two logical partitions.
+    write_mbr(0, [record(0x83, 1, 20), record(0x0f, 40, 100)])?;
+    write_mbr(40, [record(0x83, 2, 18), record(0x05, 30, 30)])?;
+    write_mbr(70, [record(0x07, 2, 20), MBRPartitionRecord::EMPTY])?;
+
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 0, 1)?;
+    let mbr = MasterBootRecord::parse(&buf)?;
+    mbr.verify(Some(200))?;
+
+    let mut buf = [0u8; 512];
+    let parts: Vec<MBRPartition> = mbr.partitions(&block, &mut buf).collect::<Result<_, _>>()?;
+    let layout: Vec<_> = parts
+        .iter()
+        .map(|part| {
+            (
+                part.number,
+                part.os_indicator,
+                part.starting_lba,
+                part.size_in_lba,
+            )
+        })
+        .collect();
+    assert_eq!(
+        layout,
+        [
+            (1, 0x83, 1, 20),
+            (2, 0x0f, 40, 100),
+            (5, 0x83, 42, 18),
+            (6, 0x07, 72, 20)
+        ]
+    );
+    assert!(parts[1].is_extended() && !parts[1].is_logical());
+    assert!(parts[3].is_logical());
+
+    // A logical partition reaching into the next EBR.
+    write_mbr(40, [record(0x83, 2, 40), record(0x05, 30, 30)])?;
+    let mut parts = mbr.partitions(&block, &mut buf);
+    assert_eq!(parts.nth(1).unwrap()?.number, 2);
+    assert!(matches!(
+        parts.next(),
+        Some(Err(GPTError::OverlappingPartitions))
+    ));
+    assert!(parts.next().is_none());
+
+    // The last EBR links back to the first one.
+    write_mbr(40, [record(0x83, 2, 18), record(0x05, 30, 30)])?;
+    write_mbr(70, [record(0x07, 2, 20), record(0x05, 0, 30)])?;
+    let parts: Vec<_> = mbr.partitions(&block, &mut buf).collect();
+    assert_eq!(parts.len(), 4);
+    assert!(matches!(parts[3], Err(GPTError::InvalidMbr)));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hybrid_mbr() -> Result<(), GPTError> {
+    copy_512_file("hybrid_mbr")?;
+    let path = tmp_512_path("hybrid_mbr");
+
+    let record = |os_indicator: u8, start: u32, size: u32| MBRPartitionRecord {
+        os_indicator,
+        starting_lba: start.to_le_bytes(),
+        size_in_lba: size.to_le_bytes(),
+        ..MBRPartitionRecord::EMPTY
+    };
+    let open_with_mbr = |partition: [MBRPartitionRecord; 4]| {
+        let block = BlockFile::<512>::open(&path)?;
+        let mut mbr = MasterBootRecord::new_protective(96);
+        mbr.partition = partition;
+        let mut buf = [0u8; 512];
+        mbr.write_to(&mut buf)?;
+        block.write(&buf, 0, 1)?;
+
+        nogpt::GPT::open(block).fail()
+    };
+    let empty = MBRPartitionRecord::EMPTY;
+
+    // The Linux partition mirrored in the first slot, the protective partition behind it.
+    let gpt = open_with_mbr([record(0x83, 34, 29), record(0xee, 1, 33), empty, empty])?;
+    assert_eq!(gpt.mbr_type(), MbrType::Hybrid);
+    let slots: Vec<usize> = gpt.hybrid_partitions().map(|(slot, _)| slot).collect();
+    assert_eq!(slots, [0]);
+    gpt.validate_hybrid_mbr()?;
+
+    // Partitions are matched by their blocks, not by their type.
+    let gpt = open_with_mbr([record(0x07, 34, 29), record(0xee, 1, 33), empty, empty])?;
+    assert_eq!(gpt.mbr_type(), MbrType::Hybrid);
+
+    let gpt = open_with_mbr([record(0x83, 34, 28), record(0xee, 1, 33), empty, empty])?;
+    assert_eq!(gpt.mbr_type(), MbrType::HybridMismatch);
+    assert!(matches!(
+        gpt.validate_hybrid_mbr(),
+        Err(GPTError::InvalidMbr)
+    ));
+
+    let gpt = open_with_mbr([empty, empty, record(0xee, 1, 95), empty])?;
+    assert_eq!(gpt.mbr_type(), MbrType::Protective);
+    assert_eq!(gpt.hybrid_partitions().count(), 0);
+
+    assert!(matches!(
+        open_with_mbr([record(0x83, 1, 95), empty, empty, empty]),
+        Err(GPTError::NoGPT)
+    ));
+
+    // Empty protective records are skipped.
+    let gpt = open_with_mbr([record(0xee, 0, 0), record(0xee, 1, 95), empty, empty])?;
+    assert_eq!(gpt.mbr_type(), MbrType::Protective);
+    assert!(matches!(
+        open_with_mbr([record(0xee, 0, 0), empty, empty, empty]),
+        Err(GPTError::NoGPT)
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn create_hybrid_mbr() -> Result<(), GPTError> {
+    let block = copy_512_file("create_hybrid_mbr")?;
+    let path = tmp_512_path("create_hybrid_mbr");
+    let read_mbr = || -> Result<MasterBootRecord, GPTError> {
+        let mut buf = [0u8; 512];
+        BlockFile::<512>::open(&path)?.read(&mut buf, 0, 1)?;
+        MasterBootRecord::parse(&buf)
+    };
+
+    let mut buf = [0u8; 512];
+    let mut gpt = nogpt::GPT::open(block).fail()?;
+    let linux = HybridPartition::new(0, 0x83).bootable(true);
+    assert!(matches!(
+        gpt.create_hybrid_mbr(&[linux; 4], &mut buf),
+        Err(GPTError::InvalidData)
+    ));
+    gpt.create_hybrid_mbr(&[linux], &mut buf)?;
+    assert_eq!(gpt.mbr_type(), MbrType::Hybrid);
+
+    let mbr = read_mbr()?;
+    let protective = mbr.partition[0];
+    assert_eq!(
+        protective.os_indicator,
+        MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE
+    );
+    assert_eq!(
+        (protective.starting_lba(), protective.size_in_lba()),
+        (1, 33)
+    );
+    let part = mbr.partition[1];
+    assert_eq!((part.os_indicator, part.boot_indicator), (0x83, 0x80));
+    assert_eq!((part.starting_lba(), part.size_in_lba()), (34, 29));
+    assert_eq!(
+        (part.start_head, part.start_sector, part.start_track),
+        (0, 35, 0)
+    );
+    assert_eq!((part.end_head, part.end_sector, part.end_track), (0, 63, 0));
+    assert!(mbr.partition[2].is_empty() && mbr.partition[3].is_empty());
+
+    let mut gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+    assert_eq!(gpt.mbr_type(), MbrType::Hybrid);
+
+    // Follow the GPT partition when it is resized.
+    gpt.resize_partition(0, 34, 50)?;
+    assert!(matches!(
+        gpt.sync_hybrid_mbr(&mut buf),
+        Err(GPTError::StagedChanges)
+    ));
+    gpt.commit()?;
+    gpt.sync_hybrid_mbr(&mut buf)?;
+    let part = read_mbr()?.partition[1];
+    assert_eq!((part.starting_lba(), part.size_in_lba()), (34, 17));
+    assert_eq!((part.end_head, part.end_sector, part.end_track), (0, 51, 0));
+    assert_eq!(part.boot_indicator, 0x80);
+
+    // Without hybrid partitions left, the MBR is protective again.
+    gpt.remove_partition(0)?;
+    gpt.commit()?;
+    gpt.sync_hybrid_mbr(&mut buf)?;
+    assert_eq!(gpt.mbr_type(), MbrType::Protective);
+    let mbr = read_mbr()?;
+    assert_eq!(mbr.partition[0].os_indicator, 0xee);
+    assert_eq!(mbr.partition[0].size_in_lba(), 95);
+    assert!(mbr.partition[1].is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn header_roundtrip() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+
+    for lba in [1, 95] {
+        let mut buf = [0u8; 512];
+        block.read(&mut buf, lba, 1)?;
+
+        let header = GPTHeader::parse(&buf)?;
+        let mut out = [0u8; 512];
+        header.write_to(&mut out)?;
+        assert_eq!(out, buf);
+
+        assert_eq!(header.to_bytes(), buf);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn part_roundtrip() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 1, 1)?;
+    let mut header = GPTHeader::parse(&buf)?;
+
+    let mut table = [0u8; 128 * 128];
+    block.read(&mut table, 2, 32)?;
+    header.validate(1, &table)?;
+
+    let mut part: GPTPartHeader = GPTPartHeader::parse(&table)?;
+    let mut slot = [0u8; 128];
+    part.write_to(&mut slot)?;
+    assert_eq!(slot, table[..128]);
+
+    part.set_name("root")?;
+    part.write_to(&mut table[..128])?;
+    header
+        .validate_part_crc(&table)
+        .expect_err("entry array was modified");
+
+    header.update_part_crc(&table)?;
+    header.validate(1, &table)?;
+
+    let part: GPTPartHeader = GPTPartHeader::parse(&table)?;
+    assert_eq!(&part.name[..5], &[114, 111, 111, 116, 0]);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn create() -> Result<(), GPTError> {
+    let gpt = create_512_file("create", 128)?;
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+
+    let header = gpt.header();
+    assert_eq!(header.guid, DISK_GUID.parse().unwrap());
+    assert_eq!(header.my_lba, 1);
+    assert_eq!(header.other_lba, 127);
+    assert_eq!(header.first_lba, 34);
+    assert_eq!(header.last_lba, 94);
+    assert_eq!(header.num_parts, 128);
+
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(part.type_guid, DefaultGPTTypeGuid::Unused);
+
+    let block = gpt.get_block();
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 0, 1)?;
+    let mbr = MasterBootRecord::parse(&buf)?;
+    mbr.verify(Some(128))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn create_options() -> Result<(), GPTError> {
+    let block = blank_512_file("create_options", 64)?;
+    let options = nogpt::GPTCreateOptions::new(nogpt::GUID::UNUSED, 64)
+        .num_parts(16)
+        .size_of_p_entry(256)
+        .first_lba(16)
+        .last_lba(40);
+
+    let gpt = nogpt::GPT::create_with_buf(block, options, &mut [0u8; 1024])?;
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+
+    let header = gpt.header();
+    assert_eq!(header.other_lba, 63);
+    assert_eq!(header.first_lba, 16);
+    assert_eq!(header.last_lba, 40);
+    assert_eq!(header.num_parts, 16);
+    assert_eq!(header.size_of_p_entry, 256);
+
+    let block = gpt.get_block();
+    let options = nogpt::GPTCreateOptions::new(nogpt::GUID::UNUSED, 64).first_lba(4);
+    assert!(
+        nogpt::GPT::create(block, options).is_err(),
+        "first lba overlaps the partition entries"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn edit_partitions() -> Result<(), GPTError> {
+    let block = copy_512_file("edit_partitions")?;
+    let mut gpt = nogpt::GPT::open(block).fail()?;
+
+    let linux_guid: nogpt::GUID = "0FC63DAF-8483-4772-8E79-3D69D8477DE4".parse().unwrap();
+    let guid: nogpt::GUID = "E7D5C0A6-4C1A-4E8B-9B7E-6C2C58F0A7B1".parse().unwrap();
+    let mut part: GPTPartHeader =
+        GPTPartHeader::new(DefaultGPTTypeGuid::ESP, guid, 40, 50, Attributes::empty());
+    part.set_name("esp")?;
+
+    assert!(matches!(
+        gpt.add_partition(&part),
+        Err(GPTError::OverlappingPartitions)
+    ));
+    gpt.remove_partition(0)?;
+    assert_eq!(gpt.add_partition(&part)?, 0);
+
+    part.start_lba = 51;
+    part.end_lba = 62;
+    assert_eq!(gpt.add_partition(&part)?, 1);
+    gpt.set_name(1, "data")?;
+    gpt.set_type(1, DefaultGPTTypeGuid::Unknown(linux_guid))?;
+
+    assert!(matches!(
+        gpt.resize_partition(1, 50, 62),
+        Err(GPTError::OverlappingPartitions)
+    ));
+    assert!(matches!(
+        gpt.resize_partition(1, 52, 63),
+        Err(GPTError::InvalidLba(63))
+    ));
+    gpt.resize_partition(1, 52, 62)?;
+    assert!(gpt.set_name(2, "unused").is_err());
+
+    // Nothing is written before commit
+    let on_disk: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(on_disk.start_lba, 34);
+
+    gpt.commit()?;
+    assert!(!gpt.has_staged_changes());
+
+    // Both headers have to be valid again
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+
+    let esp: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(esp.type_guid, DefaultGPTTypeGuid::ESP);
+    assert_eq!(esp.guid, guid);
+    assert_eq!((esp.start_lba, esp.end_lba), (40, 50));
+    assert_eq!(esp.name_str, "esp");
+
+    let data: GPTPartHeader = gpt.get_partition(1)?;
+    assert_eq!(data.type_guid, DefaultGPTTypeGuid::LinuxFilesystem);
+    assert_eq!((data.start_lba, data.end_lba), (52, 62));
+    assert_eq!(data.name_str, "data");
+
+    // Without a cache, the partition entry array read for editing has to match its crc32.
+    let block = gpt.get_block();
+    let mut buf = [0u8; 512];
+    let mut gpt = nogpt::GPT::open_with_buf(block, &mut buf).fail()?;
+    let block = BlockFile::<512>::open(&tmp_512_path("edit_partitions"))?;
+    block.read(&mut buf, 2, 1)?;
+    buf[56] ^= 0xff;
+    block.write(&buf, 2, 1)?;
+    assert!(matches!(
+        gpt.set_name(0, "broken"),
+        Err(GPTError::InvalidCrcParts(_, _))
+    ));
+    assert!(!gpt.has_staged_changes());
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn repair_main() -> Result<(), GPTError> {
+    let block = copy_512_file("repair_main")?;
+
+    // Corrupt the primary partition entry array
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 2, 1)?;
+    buf[0] ^= 0xff;
+    block.write(&buf, 2, 1)?;
+
+    let gpt = nogpt::GPT::open(block);
+    assert!(matches!(
+        gpt,
+        Err(GPTParseError::BrokenHeader(_, GptHeaderType::Main, _))
+    ));
+
+    let gpt = gpt.repair()?;
+    assert_eq!(gpt.header().my_lba, 1);
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+    assert_eq!(gpt.header().my_lba, 1);
+
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(part.name_str, "primary");
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn repair_backup() -> Result<(), GPTError> {
+    let block = copy_512_file("repair_backup")?;
+
+    // Corrupt the crc of the backup header
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 95, 1)?;
+    buf[16] ^= 0xff;
+    block.write(&buf, 95, 1)?;
+
+    let gpt = nogpt::GPT::open(block);
+    assert!(matches!(
+        gpt,
+        Err(GPTParseError::BrokenHeader(_, GptHeaderType::Backup, _))
+    ));
+
+    let gpt = nogpt::GPT::open(gpt.repair()?.get_block()).fail()?;
+
+    let block = gpt.get_block();
+    block.read(&mut buf, 95, 1)?;
+    let backup = GPTHeader::parse(&buf)?;
+    backup.validate_crc()?;
+    assert_eq!(backup.my_lba, 95);
+    assert_eq!(backup.other_lba, 1);
+    assert_eq!(backup.p_entry_lba, 63);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn large_table() -> Result<(), GPTError> {
+    // 129 entries don't fill the last block of the partition entry array, and don't fit into
+    // `DEFAULT_PARTTABLE_SIZE` bytes.
+    let block = blank_512_file("large_table", 128)?;
+    let options = nogpt::GPTCreateOptions::new(DISK_GUID.parse().unwrap(), 128).num_parts(129);
+    let mut gpt = nogpt::GPT::create(block, options)?;
+    let mut part = GPTPartHeader::new(
+        DefaultGPTTypeGuid::LinuxFilesystem,
+        part_guid(1),
+        40,
+        50,
+        Attributes::empty(),
+    );
+    part.set_name("data")?;
+    gpt.add_partition(&part)?;
+    gpt.commit()?;
+
+    // Corrupt the crc of the backup header
+    let block = gpt.get_block();
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 127, 1)?;
+    buf[16] ^= 0xff;
+    block.write(&buf, 127, 1)?;
+
+    let gpt = nogpt::GPT::open(block).repair()?;
+    let mut gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+    assert_eq!(gpt.header().num_parts, 129);
+
+    resize_512_file("large_table", 160)?;
+    gpt.relocate_backup_to_end()?;
+    let gpt = nogpt::GPT::open_sized(gpt.get_block()).fail()?;
+    assert_eq!(gpt.header().other_lba, 159);
+    let (idx, _): (u32, GPTPartHeader) = gpt.find_by_name("data")?.unwrap();
+    assert_eq!(idx, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fallback_backup() -> Result<(), GPTError> {
+    let block = copy_512_file("fallback_backup")?;
+
+    // Zero the primary header and partition entry array
+    block.write(&[0u8; 512 * 33], 1, 33)?;
+
+    let mut gpt = match nogpt::GPT::open(block) {
+        Err(GPTParseError::BrokenHeader(gpt, GptHeaderType::Main, _)) => gpt,
+        _ => panic!("primary header should be broken"),
+    };
+    assert_eq!(gpt.header().my_lba, 95);
+
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(part.name_str, "primary");
+
+    gpt.repair(GptHeaderType::Main)?;
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+    assert_eq!(gpt.header().my_lba, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn open_sized() -> Result<(), GPTError> {
+    let block = open_512_file()?;
+    assert_eq!(block.num_blocks()?, 96);
+
+    let gpt = nogpt::GPT::open_sized(block).fail()?;
+    assert_eq!(gpt.header().other_lba, 95);
+
+    // The protective MBR and the backup header reach past the end of the device
+    let block = copy_512_file("open_sized_truncated")?;
+    resize_512_file("open_sized_truncated", 80)?;
+    assert!(matches!(
+        nogpt::GPT::open_sized(block),
+        Err(GPTParseError::Error(GPTError::InvalidMbr))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn relocate_backup_to_end() -> Result<(), GPTError> {
+    let block = copy_512_file("relocate_backup_to_end")?;
+    resize_512_file("relocate_backup_to_end", 128)?;
+
+    let mut gpt = nogpt::GPT::open_sized(block).fail()?;
+    assert_eq!(gpt.header().other_lba, 95);
+
+    gpt.relocate_backup_to_end()?;
+
+    let gpt = nogpt::GPT::open_sized(gpt.get_block()).fail()?;
+    assert_eq!(gpt.header().other_lba, 127);
+    assert_eq!(gpt.header().last_lba, 94);
+
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!(part.name_str, "primary");
+
+    let block = gpt.get_block();
+    let mut buf = [0u8; 512];
+    block.read(&mut buf, 0, 1)?;
+    let mbr = MasterBootRecord::parse(&buf)?;
+    assert_eq!(mbr.partition[0].size_in_lba(), 127);
+    assert_eq!(
+        mbr.partition[0].end_chs(),
+        Chs::from_lba(127, Geometry::DEFAULT)
+    );
+
+    // A protective partition starting past the end of the device is refused.
+    let block = copy_512_file("relocate_backup_to_end_mbr")?;
+    let mut mbr = MasterBootRecord::new_protective(96);
+    mbr.partition[1] = MBRPartitionRecord::new(MBRPartitionRecord::GPT_PROTECTIVE_OS_TYPE, 200, 8);
+    mbr.write_to(&mut buf)?;
+    block.write(&buf, 0, 1)?;
+    resize_512_file("relocate_backup_to_end_mbr", 128)?;
+
+    let mut gpt = nogpt::GPT::open(block).fail()?;
+    assert!(matches!(
+        gpt.relocate_backup_to_end(),
+        Err(GPTError::InvalidMbr)
+    ));
+    assert_eq!(gpt.header().other_lba, 95);
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn grow_partition_to_max() -> Result<(), GPTError> {
+    let block = copy_512_file("grow_partition_to_max")?;
+    resize_512_file("grow_partition_to_max", 128)?;
+
+    let mut gpt = nogpt::GPT::open_sized(block).fail()?;
+    assert!(matches!(
+        gpt.grow_partition_to_max(0, 1),
+        Err(GPTError::NoFreeSpace(0))
+    ));
+
+    gpt.relocate_backup_to_end()?;
+
+    // Unrelated staged changes are not written along.
+    gpt.set_name(0, "renamed")?;
+    assert!(matches!(
+        gpt.grow_partition_to_max(0, 8),
+        Err(GPTError::StagedChanges)
+    ));
+    assert!(gpt.has_staged_changes());
+    gpt.discard();
+
+    assert_eq!(gpt.grow_partition_to_max(0, 8)?, 87);
+    assert_eq!(gpt.grow_partition_to_max(0, 1)?, 94);
+    assert!(matches!(
+        gpt.grow_partition_to_max(0, 1),
+        Err(GPTError::NoFreeSpace(0))
+    ));
+    assert!(!gpt.has_staged_changes());
+
+    let gpt = nogpt::GPT::open_sized(gpt.get_block()).fail()?;
+    let part: GPTPartHeader = gpt.get_partition(0)?;
+    assert_eq!((part.start_lba, part.end_lba), (34, 94));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ab_slots() -> Result<(), GPTError> {
+    use nogpt::ab;
+    use nogpt::part::ChromeOSAttributes;
+
+    let mut gpt = create_512_file("ab_slots", 128)?;
+
+    let kernel = DefaultGPTTypeGuid::ChromeOSKernel;
+    let slot = |priority, tries, successful| {
+        let mut cros = ChromeOSAttributes::default();
+        cros.set_priority(priority);
+        cros.set_tries(tries);
+        cros.set_successful(successful);
+        let mut attrs = Attributes::empty();
+        attrs.set_type_specific(cros.0);
+        attrs
+    };
+    let a = GPTPartHeader::new(kernel, part_guid(1), 34, 50, slot(1, 0, true));
+    let b = GPTPartHeader::new(kernel, part_guid(2), 51, 67, slot(2, 2, false));
+    gpt.add_partition(&a)?;
+    gpt.add_partition(&b)?;
+    gpt.commit()?;
+
+    let mut buf = [0u8; 512];
+    assert_eq!(ab::select_slot(&gpt, &mut buf)?.map(|s| s.idx), Some(1));
+    assert_eq!(ab::try_boot(&mut gpt, 1, &mut buf)?.attrs.tries(), 1);
+    assert_eq!(ab::try_boot(&mut gpt, 1, &mut buf)?.attrs.tries(), 0);
+
+    // Out of tries without booting successfully
+    assert_eq!(ab::select_slot(&gpt, &mut buf)?.map(|s| s.idx), Some(0));
+    let slot = ab::mark_failed(&mut gpt, 1, &mut buf)?;
+    assert_eq!(slot.attrs.priority(), 0);
+
+    assert_eq!(ab::try_boot(&mut gpt, 0, &mut buf)?.attrs.tries(), 0);
+    assert!(ab::mark_successful(&mut gpt, 0, &mut buf)?.is_bootable());
+    assert!(ab::mark_failed(&mut gpt, 2, &mut buf).is_err());
+
+    // Both headers and partition entry arrays have to be valid
+    let gpt = nogpt::GPT::open(gpt.get_block()).fail()?;
+    let part: GPTPartHeader = gpt.get_partition(1)?;
+    assert_eq!(part.attrs.type_specific(), 0);
+    let slot = ab::select_slot(&gpt, &mut buf)?.unwrap();
+    assert_eq!((slot.idx, slot.attrs.priority()), (0, 1));
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dps() -> Result<(), GPTError> {
+    use nogpt::part::{Arch, SystemdAttributes};
+
+    let mut gpt = create_512_file("dps", 128)?;
+
+    let mut add = |type_guid, start, name: &str, sd: SystemdAttributes| {
+        let mut attrs = Attributes::empty();
+        attrs.set_type_specific(sd.0);
+        let mut part =
+            GPTPartHeader::new(type_guid, part_guid(start as u16), start, start + 4, attrs);
+        part.set_name(name)?;
+        gpt.add_partition(&part)
+    };
+    let mut no_auto = SystemdAttributes::default();
+    no_auto.set_no_auto(true);
+    let mut read_only = SystemdAttributes::default();
+    read_only.set_read_only(true);
+
+    let root = DefaultGPTTypeGuid::LinuxRoot(Arch::X86_64);
+    let verity = DefaultGPTTypeGuid::LinuxRootVerity(Arch::X86_64);
+    add(DefaultGPTTypeGuid::ESP, 34, "esp", no_auto)?;
+    add(
+        DefaultGPTTypeGuid::ESP,
+        39,
+        "esp",
+        SystemdAttributes::default(),
+    )?;
+    add(root, 44, "root_1.9", SystemdAttributes::default())?;
+    add(root, 49, "root_1.10", read_only)?;
+    add(root, 54, "root_2", no_auto)?;
+    add(root, 59, "_empty", SystemdAttributes::default())?;
+    add(verity, 64, "root_1.10", SystemdAttributes::default())?;
+    add(verity, 69, "root_1.11", SystemdAttributes::default())?;
+    gpt.commit()?;
+
+    assert_eq!(gpt.find_esp()?.map(|p| p.idx), Some(1));
+    assert!(gpt.find_xbootldr()?.is_none());
+    assert!(gpt.find_usr(Arch::X86_64)?.is_none());
+    assert!(gpt.find_root_for_arch(Arch::Arm64)?.is_none());
+
+    let found = gpt.find_root_for_arch(Arch::X86_64)?.unwrap();
+    assert_eq!(found.idx, 3);
+    assert!(found.read_only() && !found.grow_fs());
+
+    // The root hash starts with the partition GUID of root_1.10 (5A1D0031-...) and ends with
+    // the one of the verity partition at block 69 (5A1D0045-...).
+    let tail = [
+        0x0D, 0x3B, 0x4C, 0x57, 0x9A, 0x52, 0x7E, 0x8D, 0x3C, 0x1B, 0x2F, 0x60,
+    ];
+    let mut root_hash = [0u8; 32];
+    root_hash[..4].copy_from_slice(&[0x5A, 0x1D, 0x00, 0x31]);
+    root_hash[4..16].copy_from_slice(&tail);
+    root_hash[16..20].copy_from_slice(&[0x5A, 0x1D, 0x00, 0x45]);
+    root_hash[20..].copy_from_slice(&tail);
+
+    let verity = gpt.find_verity_for(&found, &root_hash)?.unwrap();
+    assert_eq!(verity.idx, 7);
+    let mut buf = [0u8; 512];
+    let verity_buf = gpt.find_verity_for_with_buf(&found, &root_hash, &mut buf)?;
+    assert_eq!(verity_buf.map(|p| p.idx), Some(7));
+    assert!(gpt.find_verity_for(&verity, &root_hash).is_err());
+    assert!(gpt.find_verity_for(&found, &root_hash[..8]).is_err());
+
+    // The root hash doesn't belong to the partition.
+    root_hash[3] = 0x2C;
+    assert!(gpt.find_verity_for(&found, &root_hash)?.is_none());
+
+    Ok(())
+}
+
 #[cfg(feature = "std")]
 fn open_512_file() -> Result<BlockFile<512>, GPTError> {
-    Ok(nogpt::std::BlockFile::open(
-        &"tests/fixtures/gpt-linux-disk-01.img",
-    )?)
+    Ok(nogpt::std::BlockFile::open(&fixture_512_path())?)
+}
+
+/// Path of the image `name` in the target tmp directory, as used by [`copy_512_file`] and
+/// [`blank_512_file`].
+#[cfg(feature = "std")]
+fn tmp_512_path(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.img", name))
+}
+
+/// Copy the fixture to the target tmp directory, to allow modifying it.
+#[cfg(feature = "std")]
+fn copy_512_file(name: &str) -> Result<BlockFile<512>, GPTError> {
+    let path = tmp_512_path(name);
+    std::fs::copy(fixture_512_path(), &path)?;
+
+    Ok(nogpt::std::BlockFile::open(&path)?)
+}
+
+/// Disk guid of the partition tables created by [`create_512_file`].
+#[cfg(feature = "std")]
+const DISK_GUID: &str = "F12FC858-C753-41D3-93A4-BFAC001CDF9F";
+
+/// Create an empty partition table on a zeroed file with `blocks` blocks in the target tmp
+/// directory.
+#[cfg(feature = "std")]
+fn create_512_file(name: &str, blocks: u64) -> Result<nogpt::GPT<BlockFile<512>>, GPTError> {
+    let block = blank_512_file(name, blocks)?;
+    let options = nogpt::GPTCreateOptions::new(DISK_GUID.parse().unwrap(), blocks);
+
+    nogpt::GPT::create(block, options)
+}
+
+/// Unique partition guid number `n`, to give every partition created in a test its own guid.
+#[cfg(feature = "std")]
+fn part_guid(n: u16) -> nogpt::GUID {
+    format!("5A1D{:04X}-0D3B-4C57-9A52-7E8D3C1B2F60", n)
+        .parse()
+        .unwrap()
+}
+
+/// Create a zeroed file with `blocks` blocks in the target tmp directory.
+#[cfg(feature = "std")]
+fn blank_512_file(name: &str, blocks: u64) -> Result<BlockFile<512>, GPTError> {
+    let path = tmp_512_path(name);
+    let file = std::fs::File::create(&path)?;
+    file.set_len(blocks * 512)?;
+
+    Ok(nogpt::std::BlockFile::open(&path)?)
+}
+
+/// Grow or truncate the image `name` in the target tmp directory to `blocks` blocks.
+#[cfg(feature = "std")]
+fn resize_512_file(name: &str, blocks: u64) -> Result<(), GPTError> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(tmp_512_path(name))?
+        .set_len(blocks * 512)?;
+
+    Ok(())
 }